@@ -0,0 +1,291 @@
+use raylib::prelude::*;
+use std::path::PathBuf;
+
+use super::replay::InputFrame;
+use super::viewport::Viewport;
+
+/// The platform services a `Core` relies on while it is being initialized and updated.
+pub trait Backend {
+    fn window_should_close(&self) -> bool;
+    fn get_frame_time(&self) -> f32;
+    fn get_time(&self) -> f64;
+    fn get_screen_width(&self) -> i32;
+    fn get_screen_height(&self) -> i32;
+    fn is_key_pressed(&self, key: KeyboardKey) -> bool;
+    fn is_key_down(&self, key: KeyboardKey) -> bool;
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool;
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool;
+    fn get_mouse_position(&self) -> Vector2;
+    fn get_mouse_wheel_move(&self) -> f32;
+    fn is_window_resized(&self) -> bool;
+    fn is_window_focused(&self) -> bool;
+    /// Returns the files dropped onto the window since the last call.
+    fn take_dropped_files(&mut self) -> Vec<PathBuf>;
+    /// The raylib handle behind the window, e.g. to load textures; `None` without a window.
+    fn graphics(&mut self) -> Option<(&mut RaylibHandle, &RaylibThread)>;
+}
+
+/// A window backed by raylib.
+pub struct RaylibBackend {
+    pub(crate) rl: RaylibHandle,
+    pub(crate) thread: RaylibThread,
+    /// Stands in for the real input while input is being recorded or replayed.
+    pub(crate) input: Option<InputFrame>,
+    /// Reports the screen size and mouse position in logical space when a virtual resolution is used.
+    pub(crate) viewport: Option<Viewport>,
+}
+
+impl Backend for RaylibBackend {
+    fn window_should_close(&self) -> bool {
+        self.rl.window_should_close()
+    }
+
+    fn get_frame_time(&self) -> f32 {
+        match &self.input {
+            Some(input) => input.frame_time,
+            None => self.rl.get_frame_time(),
+        }
+    }
+
+    fn get_time(&self) -> f64 {
+        match &self.input {
+            Some(input) => input.time,
+            None => self.rl.get_time(),
+        }
+    }
+
+    fn get_screen_width(&self) -> i32 {
+        match (&self.input, &self.viewport) {
+            (Some(input), _) => input.screen_width,
+            (None, Some(viewport)) => viewport.size().0,
+            (None, None) => self.rl.get_screen_width(),
+        }
+    }
+
+    fn get_screen_height(&self) -> i32 {
+        match (&self.input, &self.viewport) {
+            (Some(input), _) => input.screen_height,
+            (None, Some(viewport)) => viewport.size().1,
+            (None, None) => self.rl.get_screen_height(),
+        }
+    }
+
+    fn is_key_pressed(&self, key: KeyboardKey) -> bool {
+        match &self.input {
+            Some(input) => input.keys_pressed.contains(&key),
+            None => self.rl.is_key_pressed(key),
+        }
+    }
+
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        match &self.input {
+            Some(input) => input.keys_down.contains(&key),
+            None => self.rl.is_key_down(key),
+        }
+    }
+
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        match &self.input {
+            Some(input) => input.buttons_pressed.contains(&button),
+            None => self.rl.is_mouse_button_pressed(button),
+        }
+    }
+
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        match &self.input {
+            Some(input) => input.buttons_down.contains(&button),
+            None => self.rl.is_mouse_button_down(button),
+        }
+    }
+
+    fn get_mouse_position(&self) -> Vector2 {
+        match (&self.input, &self.viewport) {
+            (Some(input), _) => input.mouse_position,
+            (None, Some(viewport)) => viewport.to_logical(
+                self.rl.get_screen_width(),
+                self.rl.get_screen_height(),
+                self.rl.get_mouse_position(),
+            ),
+            (None, None) => self.rl.get_mouse_position(),
+        }
+    }
+
+    fn get_mouse_wheel_move(&self) -> f32 {
+        match &self.input {
+            Some(input) => input.mouse_wheel_move,
+            None => self.rl.get_mouse_wheel_move(),
+        }
+    }
+
+    fn is_window_resized(&self) -> bool {
+        match &self.input {
+            Some(input) => input.resized,
+            None => self.rl.is_window_resized(),
+        }
+    }
+
+    fn is_window_focused(&self) -> bool {
+        match &self.input {
+            Some(input) => input.focused,
+            None => self.rl.is_window_focused(),
+        }
+    }
+
+    fn take_dropped_files(&mut self) -> Vec<PathBuf> {
+        if let Some(input) = &mut self.input {
+            return std::mem::take(&mut input.dropped_files);
+        }
+
+        if !self.rl.is_file_dropped() {
+            return Vec::new();
+        }
+
+        let files = self.rl.get_dropped_files();
+        self.rl.clear_dropped_files();
+
+        files.into_iter().map(PathBuf::from).collect()
+    }
+
+    fn graphics(&mut self) -> Option<(&mut RaylibHandle, &RaylibThread)> {
+        Some((&mut self.rl, &self.thread))
+    }
+}
+
+/// A backend without a window; time only moves when a frame ends and all input is simulated.
+pub struct HeadlessBackend {
+    pub(crate) input: InputFrame,
+    should_close: bool,
+}
+
+impl HeadlessBackend {
+    pub fn new(screen_width: i32, screen_height: i32) -> Self {
+        HeadlessBackend {
+            input: InputFrame::new(screen_width, screen_height),
+            should_close: false,
+        }
+    }
+
+    /// Sets how much simulated time passes every frame.
+    pub fn set_frame_time(&mut self, frame_time: f32) {
+        self.input.frame_time = frame_time;
+    }
+
+    pub fn set_screen_size(&mut self, screen_width: i32, screen_height: i32) {
+        let input = &mut self.input;
+        input.resized = input.screen_width != screen_width || input.screen_height != screen_height;
+        input.screen_width = screen_width;
+        input.screen_height = screen_height;
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.input.focused = focused;
+    }
+
+    pub fn drop_file(&mut self, path: &str) {
+        self.input.dropped_files.push(PathBuf::from(path));
+    }
+
+    pub fn close(&mut self) {
+        self.should_close = true;
+    }
+
+    pub fn press_key(&mut self, key: KeyboardKey) {
+        if self.input.keys_down.insert(key) {
+            self.input.keys_pressed.insert(key);
+        }
+    }
+
+    pub fn release_key(&mut self, key: KeyboardKey) {
+        self.input.keys_down.remove(&key);
+    }
+
+    pub fn press_mouse_button(&mut self, button: MouseButton) {
+        if self.input.buttons_down.insert(button) {
+            self.input.buttons_pressed.insert(button);
+        }
+    }
+
+    pub fn release_mouse_button(&mut self, button: MouseButton) {
+        self.input.buttons_down.remove(&button);
+    }
+
+    pub fn set_mouse_position(&mut self, position: Vector2) {
+        self.input.mouse_position = position;
+    }
+
+    pub fn scroll(&mut self, amount: f32) {
+        self.input.mouse_wheel_move += amount;
+    }
+
+    /// Advances time and forgets anything that only lasts a single frame, e.g. key presses.
+    pub fn end_frame(&mut self) {
+        let input = &mut self.input;
+        input.time += input.frame_time as f64;
+        input.keys_pressed.clear();
+        input.buttons_pressed.clear();
+        input.mouse_wheel_move = 0.0;
+        input.resized = false;
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn window_should_close(&self) -> bool {
+        self.should_close
+    }
+
+    fn get_frame_time(&self) -> f32 {
+        self.input.frame_time
+    }
+
+    fn get_time(&self) -> f64 {
+        self.input.time
+    }
+
+    fn get_screen_width(&self) -> i32 {
+        self.input.screen_width
+    }
+
+    fn get_screen_height(&self) -> i32 {
+        self.input.screen_height
+    }
+
+    fn is_key_pressed(&self, key: KeyboardKey) -> bool {
+        self.input.keys_pressed.contains(&key)
+    }
+
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        self.input.keys_down.contains(&key)
+    }
+
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.input.buttons_pressed.contains(&button)
+    }
+
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.input.buttons_down.contains(&button)
+    }
+
+    fn get_mouse_position(&self) -> Vector2 {
+        self.input.mouse_position
+    }
+
+    fn get_mouse_wheel_move(&self) -> f32 {
+        self.input.mouse_wheel_move
+    }
+
+    fn is_window_resized(&self) -> bool {
+        self.input.resized
+    }
+
+    fn is_window_focused(&self) -> bool {
+        self.input.focused
+    }
+
+    fn take_dropped_files(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.input.dropped_files)
+    }
+
+    fn graphics(&mut self) -> Option<(&mut RaylibHandle, &RaylibThread)> {
+        None
+    }
+}
@@ -0,0 +1,359 @@
+use raylib::prelude::*;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+use super::assets::{Asset, Assets, Handle};
+use super::backend::Backend;
+use super::debug::Watches;
+use super::random::Random;
+use super::replay::Replay;
+use super::runner::Runner;
+use super::tween::{TweenHandle, Tweenable, Tweens};
+
+/// Everything a `Core` needs to know about the current frame while it is updating.
+///
+/// `Context` is a `Backend` itself, so input can be queried through it directly, e.g. by an `InputMap`.
+pub struct Context<'a> {
+    backend: &'a mut dyn Backend,
+    delta_time: f32,
+    total_time: f64,
+    frame: u64,
+    watches: Option<&'a mut Watches>,
+    assets: Option<&'a mut Assets>,
+    replay: Option<&'a mut Replay>,
+    tweens: Option<&'a mut Tweens>,
+    random: Option<&'a mut Random>,
+    own_random: Random,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(backend: &'a mut dyn Backend, delta_time: f32, frame: u64) -> Self {
+        let total_time = backend.get_time();
+
+        Context {
+            backend,
+            delta_time,
+            total_time,
+            frame,
+            watches: None,
+            assets: None,
+            replay: None,
+            tweens: None,
+            random: None,
+            own_random: Random::new(0),
+        }
+    }
+
+    pub(crate) fn with_watches(mut self, watches: &'a mut Watches) -> Self {
+        self.watches = Some(watches);
+        self
+    }
+
+    pub(crate) fn with_assets(mut self, assets: &'a mut Assets) -> Self {
+        self.assets = Some(assets);
+        self
+    }
+
+    pub(crate) fn with_replay(mut self, replay: &'a mut Replay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    pub(crate) fn with_tweens(mut self, tweens: &'a mut Tweens) -> Self {
+        self.tweens = Some(tweens);
+        self
+    }
+
+    pub(crate) fn with_random(mut self, random: &'a mut Random) -> Self {
+        self.random = Some(random);
+        self
+    }
+
+    /// Shows `value` next to `name` in the debug overlay until it is watched again.
+    pub fn watch<T: Display>(&mut self, name: &str, value: T) {
+        if let Some(watches) = &mut self.watches {
+            watches.set(name, &value);
+        }
+    }
+
+    /// Loads the file at `path` once; it is reloaded whenever it changes and `Core::on_asset_reloaded` is called.
+    pub fn load<T: Asset>(&mut self, path: &str) -> Result<Handle<T>, Box<dyn Error>> {
+        let assets = self
+            .assets
+            .as_deref_mut()
+            .ok_or("There are no assets to load into.")?;
+        assets.load(&mut *self.backend, path)
+    }
+
+    pub fn asset<T: Asset>(&self, handle: Handle<T>) -> Option<&T> {
+        self.assets.as_deref()?.get(handle)
+    }
+
+    /// The tweens the app advances every frame; `None` for a `Context` that was created by hand.
+    pub fn tweens(&mut self) -> Option<&mut Tweens> {
+        self.tweens.as_deref_mut()
+    }
+
+    /// The app's random number generator, seeded through `AppConfig::seed` and replayed along with recorded input.
+    ///
+    /// A `Context` that was created by hand has a generator of its own, seeded with zero.
+    pub fn random(&mut self) -> &mut Random {
+        match self.random.as_deref_mut() {
+            Some(random) => random,
+            None => &mut self.own_random,
+        }
+    }
+
+    /// Registers the seed of a random number generator under `name` and returns the seed to use instead.
+    ///
+    /// The seed is stored alongside recorded input, and replaced by the stored one when that input is replayed.
+    pub fn seed(&mut self, name: &str, seed: u64) -> u64 {
+        match &mut self.replay {
+            Some(replay) => replay.seed(name, seed),
+            None => seed,
+        }
+    }
+
+    /// Seconds since the previous update; during `fixed_update` this is always the fixed timestep.
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    /// Seconds since the app started.
+    pub fn total_time(&self) -> f64 {
+        self.total_time
+    }
+
+    /// How many frames came before this one.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn screen_size(&self) -> (i32, i32) {
+        (
+            self.backend.get_screen_width(),
+            self.backend.get_screen_height(),
+        )
+    }
+
+    pub fn backend(&self) -> &dyn Backend {
+        self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut dyn Backend {
+        self.backend
+    }
+}
+
+impl<'a> Backend for Context<'a> {
+    fn window_should_close(&self) -> bool {
+        self.backend.window_should_close()
+    }
+
+    fn get_frame_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    fn get_time(&self) -> f64 {
+        self.total_time
+    }
+
+    fn get_screen_width(&self) -> i32 {
+        self.backend.get_screen_width()
+    }
+
+    fn get_screen_height(&self) -> i32 {
+        self.backend.get_screen_height()
+    }
+
+    fn is_key_pressed(&self, key: KeyboardKey) -> bool {
+        self.backend.is_key_pressed(key)
+    }
+
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        self.backend.is_key_down(key)
+    }
+
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.backend.is_mouse_button_pressed(button)
+    }
+
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.backend.is_mouse_button_down(button)
+    }
+
+    fn get_mouse_position(&self) -> Vector2 {
+        self.backend.get_mouse_position()
+    }
+
+    fn get_mouse_wheel_move(&self) -> f32 {
+        self.backend.get_mouse_wheel_move()
+    }
+
+    fn is_window_resized(&self) -> bool {
+        self.backend.is_window_resized()
+    }
+
+    fn is_window_focused(&self) -> bool {
+        self.backend.is_window_focused()
+    }
+
+    fn take_dropped_files(&mut self) -> Vec<PathBuf> {
+        self.backend.take_dropped_files()
+    }
+
+    fn graphics(&mut self) -> Option<(&mut RaylibHandle, &RaylibThread)> {
+        self.backend.graphics()
+    }
+}
+
+/// Everything a `Core` needs to know about the current frame while it is drawing.
+///
+/// Like `Context`, it reports the screen size and mouse position in logical space, so draw code and update code
+/// agree on where things are when there is a virtual resolution, and the time as the runner saw it, so replayed
+/// sessions draw with their recorded time.
+///
+/// `Frame` can draw anything a raylib draw handle can, and dereferences to one for everything else. Anything
+/// reached through that `Deref` is raylib's own API rather than rna's, and ties the drawing code to raylib.
+pub struct Frame<'a, 'b> {
+    d: &'a mut RaylibDrawHandle<'b>,
+    thread: &'a RaylibThread,
+    assets: &'a Assets,
+    tweens: &'a Tweens,
+    alpha: f32,
+    delta_time: f32,
+    total_time: f64,
+    frame: u64,
+    screen_size: (i32, i32),
+    mouse_position: Vector2,
+}
+
+impl<'a, 'b> Frame<'a, 'b> {
+    pub(crate) fn new(
+        d: &'a mut RaylibDrawHandle<'b>,
+        thread: &'a RaylibThread,
+        runner: &'a Runner,
+        alpha: f32,
+        screen_size: (i32, i32),
+    ) -> Self {
+        let mouse_position = d.get_mouse_position();
+
+        Frame {
+            d,
+            thread,
+            assets: &runner.services.assets,
+            tweens: &runner.services.tweens,
+            alpha,
+            delta_time: runner.delta_time(),
+            total_time: runner.total_time(),
+            frame: runner.frame(),
+            screen_size,
+            mouse_position,
+        }
+    }
+
+    pub(crate) fn with_mouse_position(mut self, mouse_position: Vector2) -> Self {
+        self.mouse_position = mouse_position;
+        self
+    }
+
+    /// How far the app is between the previous and the next fixed update, from zero to one.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    pub fn total_time(&self) -> f64 {
+        self.total_time
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The size of what is being drawn to, which is the virtual resolution if there is one.
+    pub fn screen_size(&self) -> (i32, i32) {
+        self.screen_size
+    }
+
+    /// Shadows the draw handle's window width, which differs from the logical one with a virtual resolution.
+    pub fn get_screen_width(&self) -> i32 {
+        self.screen_size.0
+    }
+
+    /// Shadows the draw handle's window height, which differs from the logical one with a virtual resolution.
+    pub fn get_screen_height(&self) -> i32 {
+        self.screen_size.1
+    }
+
+    /// The mouse in the same space as `screen_size`, as `Context` reports it.
+    pub fn mouse_position(&self) -> Vector2 {
+        self.mouse_position
+    }
+
+    /// Shadows the draw handle's mouse position, which is in window space.
+    pub fn get_mouse_position(&self) -> Vector2 {
+        self.mouse_position
+    }
+
+    pub fn thread(&self) -> &RaylibThread {
+        self.thread
+    }
+
+    /// Assets outlive the frame, so a texture can be drawn while it is borrowed.
+    pub fn asset<T: Asset>(&self, handle: Handle<T>) -> Option<&'a T> {
+        self.assets.get(handle)
+    }
+
+    /// The current value of a tween, as of the latest update.
+    pub fn tween<T: Tweenable>(&self, handle: TweenHandle<T>) -> Option<T> {
+        self.tweens.value(handle)
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.d.clear_background(color);
+    }
+
+    pub fn rectangle(&mut self, bounds: Rectangle, color: Color) {
+        self.d.draw_rectangle_rec(bounds, color);
+    }
+
+    pub fn rectangle_lines(&mut self, bounds: Rectangle, thickness: i32, color: Color) {
+        self.d.draw_rectangle_lines_ex(bounds, thickness, color);
+    }
+
+    pub fn line(&mut self, start: Vector2, end: Vector2, color: Color) {
+        self.d.draw_line_v(start, end, color);
+    }
+
+    pub fn circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        self.d.draw_circle_v(center, radius, color);
+    }
+
+    pub fn text(&mut self, text: &str, position: Vector2, font_size: i32, color: Color) {
+        self.d
+            .draw_text(text, position.x as i32, position.y as i32, font_size, color);
+    }
+}
+
+impl<'a, 'b> RaylibDraw for Frame<'a, 'b> {}
+
+/// Reaches raylib's draw handle directly; code that relies on it only runs with the raylib backend.
+impl<'a, 'b> Deref for Frame<'a, 'b> {
+    type Target = RaylibDrawHandle<'b>;
+
+    fn deref(&self) -> &Self::Target {
+        self.d
+    }
+}
+
+impl<'a, 'b> DerefMut for Frame<'a, 'b> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.d
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// Everything that can go wrong while setting up an `App`.
+#[derive(Debug)]
+pub enum RnaError {
+    /// The config never received a `Core` to run.
+    MissingCore,
+    /// Windows need a positive width and height.
+    InvalidWindowSize(i32, i32),
+    /// The title contains a NUL byte, which the windowing system cannot display.
+    InvalidTitle(String),
+    /// raylib only supports a single window per process.
+    AlreadyRunning,
+    /// The platform refused to open a window, e.g. because there is no display.
+    WindowCreation(String),
+    /// The input recording to replay is missing or malformed.
+    InvalidRecording(String, String),
+    /// A config file could not be read or contains an unknown setting.
+    InvalidConfig(String, String),
+    /// The window icon could not be loaded.
+    InvalidIcon(String, String),
+}
+
+impl fmt::Display for RnaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RnaError::MissingCore => write!(f, "an RNA Core was not present"),
+            RnaError::InvalidWindowSize(width, height) => {
+                write!(f, "invalid window size {}x{}", width, height)
+            }
+            RnaError::InvalidTitle(title) => write!(f, "invalid window title {:?}", title),
+            RnaError::AlreadyRunning => write!(f, "an RNA App has already been built"),
+            RnaError::WindowCreation(reason) => write!(f, "could not create a window: {}", reason),
+            RnaError::InvalidRecording(path, reason) => {
+                write!(f, "could not replay input from '{}': {}", path, reason)
+            }
+            RnaError::InvalidConfig(path, reason) => {
+                write!(f, "could not load config from '{}': {}", path, reason)
+            }
+            RnaError::InvalidIcon(path, reason) => {
+                write!(f, "could not load window icon '{}': {}", path, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RnaError {}
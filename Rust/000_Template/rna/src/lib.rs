@@ -1,15 +1,64 @@
 mod app_config;
-pub use self::app_config::AppConfig;
+pub use self::app_config::{AppConfig, AppConfigBuilder};
 
 mod app;
 pub use self::app::App;
 
+mod assets;
+pub use self::assets::{Asset, Assets, Handle};
+
+mod backend;
+pub use self::backend::{Backend, HeadlessBackend, RaylibBackend};
+
+mod capture;
+pub use self::capture::CaptureFormat;
+
+mod color_map;
+pub use self::color_map::{ColorMap, Scaling};
+
+mod context;
+pub use self::context::{Context, Frame};
+
 mod core;
 pub use self::core::Core;
 
+mod debug;
+
+mod easing;
+pub use self::easing::Easing;
+
+mod error;
+pub use self::error::RnaError;
+
+mod headless;
+pub use self::headless::HeadlessApp;
+
+mod input;
+pub use self::input::{Binding, InputMap};
+
+mod lifecycle;
+
 mod math_ext;
 pub use self::math_ext::*;
 
+mod random;
+pub use self::random::{Random, RandomRange};
+
+mod replay;
+
+mod runner;
+
+mod scene;
+pub use self::scene::{SceneStack, Transition};
+
+mod timestep;
+
+mod tween;
+pub use self::tween::{Tween, TweenHandle, Tweenable, Tweens};
+
+mod viewport;
+pub use self::viewport::{ScaleMode, Viewport};
+
 // #[cfg(test)]
 // mod tests {
 //     #[test]
@@ -0,0 +1,29 @@
+use super::backend::Backend;
+use super::core::Core;
+
+/// Watches the window for changes and turns them into calls to the lifecycle hooks of a `Core`.
+pub(crate) struct Lifecycle {
+    focused: bool,
+}
+
+impl Lifecycle {
+    pub(crate) fn new() -> Self {
+        Lifecycle { focused: true }
+    }
+
+    pub(crate) fn dispatch(&mut self, core: &mut dyn Core, backend: &mut dyn Backend) {
+        if backend.is_window_resized() {
+            core.on_resize(backend.get_screen_width(), backend.get_screen_height());
+        }
+
+        let focused = backend.is_window_focused();
+        if focused != self.focused {
+            self.focused = focused;
+            core.on_focus_changed(focused);
+        }
+
+        for path in backend.take_dropped_files() {
+            core.on_file_dropped(&path);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use raylib::prelude::*;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::backend::Backend;
+use super::input::{Binding, KEYS, MOUSE_BUTTONS};
+
+/// Everything a `Core` can learn from the backend during a single frame.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct InputFrame {
+    pub(crate) time: f64,
+    pub(crate) frame_time: f32,
+    pub(crate) screen_width: i32,
+    pub(crate) screen_height: i32,
+    pub(crate) keys_down: HashSet<KeyboardKey>,
+    pub(crate) keys_pressed: HashSet<KeyboardKey>,
+    pub(crate) buttons_down: HashSet<MouseButton>,
+    pub(crate) buttons_pressed: HashSet<MouseButton>,
+    pub(crate) mouse_position: Vector2,
+    pub(crate) mouse_wheel_move: f32,
+    pub(crate) resized: bool,
+    pub(crate) focused: bool,
+    pub(crate) dropped_files: Vec<PathBuf>,
+}
+
+impl InputFrame {
+    pub(crate) fn new(screen_width: i32, screen_height: i32) -> Self {
+        InputFrame {
+            time: 0.0,
+            frame_time: 1. / 60.,
+            screen_width,
+            screen_height,
+            keys_down: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            buttons_down: HashSet::new(),
+            buttons_pressed: HashSet::new(),
+            mouse_position: Vector2::new(0.0, 0.0),
+            mouse_wheel_move: 0.0,
+            resized: false,
+            focused: true,
+            dropped_files: Vec::new(),
+        }
+    }
+
+    /// Asks `backend` about every key and button it knows; dropped files are taken from it.
+    pub(crate) fn capture(backend: &mut dyn Backend) -> Self {
+        let mut frame = InputFrame::new(backend.get_screen_width(), backend.get_screen_height());
+        frame.time = backend.get_time();
+        frame.frame_time = backend.get_frame_time();
+
+        for (_, key) in KEYS.iter() {
+            if backend.is_key_down(*key) {
+                frame.keys_down.insert(*key);
+            }
+            if backend.is_key_pressed(*key) {
+                frame.keys_pressed.insert(*key);
+            }
+        }
+
+        for (_, button) in MOUSE_BUTTONS.iter() {
+            if backend.is_mouse_button_down(*button) {
+                frame.buttons_down.insert(*button);
+            }
+            if backend.is_mouse_button_pressed(*button) {
+                frame.buttons_pressed.insert(*button);
+            }
+        }
+
+        frame.mouse_position = backend.get_mouse_position();
+        frame.mouse_wheel_move = backend.get_mouse_wheel_move();
+        frame.resized = backend.is_window_resized();
+        frame.focused = backend.is_window_focused();
+        frame.dropped_files = backend.take_dropped_files();
+
+        frame
+    }
+}
+
+fn names(keys: &HashSet<KeyboardKey>, buttons: &HashSet<MouseButton>) -> String {
+    let mut names: Vec<&str> = keys
+        .iter()
+        .map(|key| Binding::Key(*key).name())
+        .chain(
+            buttons
+                .iter()
+                .map(|button| Binding::MouseButton(*button).name()),
+        )
+        .collect();
+    names.sort_unstable();
+    names.join(",")
+}
+
+fn parse<T: FromStr>(value: Option<&str>, number: usize, what: &str) -> Result<T, String> {
+    value.and_then(|value| value.parse().ok()).ok_or(format!(
+        "line {}: could not parse {}",
+        number + 1,
+        what
+    ))
+}
+
+fn parse_pair<T: FromStr>(
+    value: Option<&str>,
+    number: usize,
+    what: &str,
+) -> Result<(T, T), String> {
+    let mut values = value.unwrap_or("").split(',');
+    let first = parse(values.next(), number, what)?;
+    let second = parse(values.next(), number, what)?;
+
+    Ok((first, second))
+}
+
+/// Input and random seeds of a whole session, stored one frame after another.
+#[derive(Default)]
+pub(crate) struct Recording {
+    seeds: Vec<(String, u64)>,
+    frames: Vec<InputFrame>,
+}
+
+impl Recording {
+    pub(crate) fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut recording = Recording::default();
+
+        for (number, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (tag, data) = line.split_once(' ').unwrap_or((line, ""));
+
+            if tag == "frame" {
+                let mut values = data.split_whitespace();
+                let mut frame = InputFrame::new(0, 0);
+                frame.time = parse(values.next(), number, "time")?;
+                frame.frame_time = parse(values.next(), number, "frame time")?;
+                let (width, height) = parse_pair(values.next(), number, "screen size")?;
+                frame.screen_width = width;
+                frame.screen_height = height;
+                let (x, y) = parse_pair(values.next(), number, "mouse position")?;
+                frame.mouse_position = Vector2::new(x, y);
+                frame.mouse_wheel_move = parse(values.next(), number, "mouse wheel")?;
+                frame.focused = parse::<u8>(values.next(), number, "focus")? != 0;
+
+                recording.frames.push(frame);
+                continue;
+            }
+
+            if tag == "seed" {
+                let (name, seed) = data
+                    .split_once(' ')
+                    .ok_or(format!("line {}: expected a name and a seed", number + 1))?;
+                recording
+                    .seeds
+                    .push((String::from(name), parse(Some(seed), number, "seed")?));
+                continue;
+            }
+
+            let frame = recording
+                .frames
+                .last_mut()
+                .ok_or(format!("line {}: expected a frame first", number + 1))?;
+
+            match tag {
+                "resized" => frame.resized = true,
+                "drop" => frame.dropped_files.push(PathBuf::from(data)),
+                "down" | "pressed" => {
+                    for name in data.split(',') {
+                        let binding = Binding::from_name(name).ok_or(format!(
+                            "line {}: unknown binding '{}'",
+                            number + 1,
+                            name
+                        ))?;
+
+                        let (keys, buttons) = if tag == "down" {
+                            (&mut frame.keys_down, &mut frame.buttons_down)
+                        } else {
+                            (&mut frame.keys_pressed, &mut frame.buttons_pressed)
+                        };
+
+                        match binding {
+                            Binding::Key(key) => keys.insert(key),
+                            Binding::MouseButton(button) => buttons.insert(button),
+                            // The wheel is recorded as part of the frame itself.
+                            _ => {
+                                return Err(format!(
+                                    "line {}: unexpected binding '{}'",
+                                    number + 1,
+                                    name
+                                )
+                                .into())
+                            }
+                        };
+                    }
+                }
+                _ => return Err(format!("line {}: unknown entry '{}'", number + 1, tag).into()),
+            }
+        }
+
+        Ok(recording)
+    }
+
+    pub(crate) fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut file = File::create(path)?;
+
+        writeln!(
+            file,
+            "# frame <time> <frame time> <width>,<height> <mouse x>,<mouse y> <wheel> <focused>"
+        )?;
+
+        for (name, seed) in self.seeds.iter() {
+            writeln!(file, "seed {} {}", name, seed)?;
+        }
+
+        for frame in self.frames.iter() {
+            writeln!(
+                file,
+                "frame {} {} {},{} {},{} {} {}",
+                frame.time,
+                frame.frame_time,
+                frame.screen_width,
+                frame.screen_height,
+                frame.mouse_position.x,
+                frame.mouse_position.y,
+                frame.mouse_wheel_move,
+                frame.focused as u8
+            )?;
+
+            if frame.resized {
+                writeln!(file, "resized")?;
+            }
+
+            let down = names(&frame.keys_down, &frame.buttons_down);
+            if !down.is_empty() {
+                writeln!(file, "down {}", down)?;
+            }
+
+            let pressed = names(&frame.keys_pressed, &frame.buttons_pressed);
+            if !pressed.is_empty() {
+                writeln!(file, "pressed {}", pressed)?;
+            }
+
+            for path in frame.dropped_files.iter() {
+                writeln!(file, "drop {}", path.to_string_lossy())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records the input of every frame to a file, or plays a recording back in place of the real input.
+pub(crate) enum Replay {
+    Off,
+    Recording { path: String, recording: Recording },
+    Playing { recording: Recording, next: usize },
+}
+
+impl Replay {
+    pub(crate) fn play(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Replay::Playing {
+            recording: Recording::load(path)?,
+            next: 0,
+        })
+    }
+
+    pub(crate) fn record(path: &str) -> Self {
+        Replay::Recording {
+            path: String::from(path),
+            recording: Recording::default(),
+        }
+    }
+
+    /// Whether there are recorded frames left to play.
+    pub(crate) fn has_next(&self) -> bool {
+        match self {
+            Replay::Playing { recording, next } => *next < recording.frames.len(),
+            _ => false,
+        }
+    }
+
+    /// The input to use for the coming frame, or `None` to keep using the real input.
+    ///
+    /// Once a recording has been played back completely, the real input takes over again.
+    pub(crate) fn next_frame(&mut self, backend: &mut dyn Backend) -> Option<InputFrame> {
+        match self {
+            Replay::Off => None,
+            Replay::Recording { recording, .. } => {
+                let frame = InputFrame::capture(backend);
+                recording.frames.push(frame.clone());
+                Some(frame)
+            }
+            Replay::Playing { recording, next } => {
+                let frame = recording.frames.get(*next).cloned();
+                *next += 1;
+
+                if frame.is_none() {
+                    *self = Replay::Off;
+                }
+                frame
+            }
+        }
+    }
+
+    /// Remembers `seed` while recording; while playing, returns the seed recorded under `name` instead.
+    pub(crate) fn seed(&mut self, name: &str, seed: u64) -> u64 {
+        match self {
+            Replay::Off => seed,
+            Replay::Recording { recording, .. } => {
+                match recording.seeds.iter_mut().find(|(other, _)| other == name) {
+                    Some((_, current)) => *current = seed,
+                    None => recording.seeds.push((String::from(name), seed)),
+                }
+                seed
+            }
+            Replay::Playing { recording, .. } => recording
+                .seeds
+                .iter()
+                .find(|(other, _)| other == name)
+                .map_or(seed, |(_, recorded)| *recorded),
+        }
+    }
+
+    /// Saves the recording, if there is one.
+    pub(crate) fn finish(&mut self) {
+        if let Replay::Recording { path, recording } = self {
+            if let Err(error) = recording.save(path) {
+                eprintln!("Could not save input recording to '{}': {}", path, error);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::assets::Assets;
+use super::backend::Backend;
+use super::context::Context;
+use super::core::Core;
+use super::debug::Watches;
+use super::lifecycle::Lifecycle;
+use super::random::{self, Random};
+use super::replay::Replay;
+use super::timestep::FixedTimestep;
+use super::tween::Tweens;
+
+/// Everything rna looks after on behalf of a `Core`, lent to it through every `Context`.
+pub(crate) struct Services {
+    pub(crate) watches: Watches,
+    pub(crate) assets: Assets,
+    pub(crate) replay: Replay,
+    pub(crate) tweens: Tweens,
+    pub(crate) random: Random,
+}
+
+impl Services {
+    pub(crate) fn context<'a>(
+        &'a mut self,
+        backend: &'a mut dyn Backend,
+        delta_time: f32,
+        frame: u64,
+    ) -> Context<'a> {
+        Context::new(backend, delta_time, frame)
+            .with_watches(&mut self.watches)
+            .with_assets(&mut self.assets)
+            .with_replay(&mut self.replay)
+            .with_tweens(&mut self.tweens)
+            .with_random(&mut self.random)
+    }
+}
+
+/// The part of a frame that `App` and `HeadlessApp` have in common: everything but input and drawing.
+pub(crate) struct Runner {
+    pub(crate) services: Services,
+    timestep: FixedTimestep,
+    lifecycle: Lifecycle,
+    frame: u64,
+    delta_time: f32,
+    total_time: f64,
+}
+
+impl Runner {
+    pub(crate) fn new(services: Services, timestep: FixedTimestep) -> Self {
+        Runner {
+            services,
+            timestep,
+            lifecycle: Lifecycle::new(),
+            frame: 0,
+            delta_time: 0.0,
+            total_time: 0.0,
+        }
+    }
+
+    /// How many frames came before the current one.
+    pub(crate) fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The frame time of the latest update, which is the recorded one while input is replayed.
+    pub(crate) fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    /// The time of the latest update, which is the recorded one while input is replayed.
+    pub(crate) fn total_time(&self) -> f64 {
+        self.total_time
+    }
+
+    pub(crate) fn set_timestep(&mut self, timestep: FixedTimestep) {
+        self.timestep = timestep;
+    }
+
+    /// Seeds the random number generator, which a replayed session overrides, and initializes the core.
+    pub(crate) fn initialize(&mut self, core: &mut dyn Core, backend: &mut dyn Backend) {
+        let services = &mut self.services;
+        services.random = Random::new(
+            services
+                .replay
+                .seed(random::SEED_NAME, services.random.seed()),
+        );
+
+        let delta_time = backend.get_frame_time();
+        core.initialize(&mut services.context(backend, delta_time, 0));
+    }
+
+    /// Dispatches window events and reloaded assets, advances the tweens, and updates the core, followed by as
+    /// many fixed updates as are due. Returns the interpolation alpha for `draw`.
+    pub(crate) fn update(&mut self, core: &mut dyn Core, backend: &mut dyn Backend) -> f32 {
+        let (services, frame) = (&mut self.services, self.frame);
+        let delta_time = backend.get_frame_time();
+        self.delta_time = delta_time;
+        self.total_time = backend.get_time();
+
+        self.lifecycle.dispatch(core, backend);
+
+        for path in services.assets.poll(backend) {
+            core.on_asset_reloaded(&mut services.context(backend, delta_time, frame), &path);
+        }
+
+        services.tweens.update(delta_time);
+        core.update(&mut services.context(backend, delta_time, frame));
+
+        let timestep = self.timestep.timestep();
+        self.timestep.advance(delta_time, || {
+            core.fixed_update(&mut services.context(backend, timestep, frame))
+        })
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Saves recorded input and shuts the core down.
+    pub(crate) fn shutdown(&mut self, core: &mut dyn Core) {
+        self.services.replay.finish();
+        core.shutdown();
+    }
+}
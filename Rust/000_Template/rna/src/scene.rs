@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use super::context::{Context, Frame};
+use super::core::Core;
+
+/// A change to the scene stack, requested by the scene on top through `Core::transition`.
+pub enum Transition {
+    /// Puts a new scene on top of the current one.
+    Push(Box<dyn Core>),
+    /// Removes the current scene; the app closes once the last scene is gone.
+    Pop,
+    /// Removes the current scene and puts a new one in its place.
+    Replace(Box<dyn Core>),
+}
+
+/// A stack of `Core`s where only the top of the stack is in control.
+///
+/// Scenes are updated from the top down, stopping at the first one that `is_modal`, and drawn from the bottom up,
+/// starting at the first one that is not an `is_overlay`.
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Core>>,
+    pending: Vec<Box<dyn Core>>,
+}
+
+impl SceneStack {
+    pub fn new(scene: Box<dyn Core>) -> Self {
+        SceneStack {
+            scenes: Vec::new(),
+            pending: vec![scene],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.scenes.len() + self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, context: &mut Context, mut scene: Box<dyn Core>) {
+        scene.initialize(context);
+        scene.enter(context);
+        self.scenes.push(scene);
+    }
+
+    fn pop(&mut self, context: &mut Context) {
+        if let Some(mut scene) = self.scenes.pop() {
+            scene.exit(context);
+        }
+    }
+
+    fn apply(&mut self, context: &mut Context, transition: Transition) {
+        match transition {
+            Transition::Push(scene) => self.push(context, scene),
+            Transition::Pop => self.pop(context),
+            Transition::Replace(scene) => {
+                self.pop(context);
+                self.push(context, scene);
+            }
+        }
+    }
+
+    /// Updates the active scenes with `update` and then carries out whatever transition the top scene asked for.
+    fn update_with<F: FnMut(&mut dyn Core, &mut Context)>(
+        &mut self,
+        context: &mut Context,
+        mut update: F,
+    ) {
+        for scene in self.scenes.iter_mut().rev() {
+            update(scene.as_mut(), context);
+
+            if scene.is_modal() {
+                break;
+            }
+        }
+
+        let transition = self.scenes.last_mut().and_then(|scene| scene.transition());
+
+        if let Some(transition) = transition {
+            self.apply(context, transition);
+        }
+    }
+}
+
+impl Core for SceneStack {
+    fn initialize(&mut self, context: &mut Context) {
+        for scene in std::mem::take(&mut self.pending) {
+            self.push(context, scene);
+        }
+    }
+
+    fn update(&mut self, context: &mut Context) {
+        self.update_with(context, |scene, context| scene.update(context));
+    }
+
+    fn fixed_update(&mut self, context: &mut Context) {
+        self.update_with(context, |scene, context| scene.fixed_update(context));
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let bottom = self
+            .scenes
+            .iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+
+        for scene in self.scenes.iter().skip(bottom) {
+            scene.draw(frame);
+        }
+    }
+    fn on_resize(&mut self, width: i32, height: i32) {
+        for scene in self.scenes.iter_mut() {
+            scene.on_resize(width, height);
+        }
+    }
+
+    fn on_focus_changed(&mut self, focused: bool) {
+        for scene in self.scenes.iter_mut() {
+            scene.on_focus_changed(focused);
+        }
+    }
+
+    fn on_file_dropped(&mut self, path: &Path) {
+        if let Some(scene) = self.scenes.last_mut() {
+            scene.on_file_dropped(path);
+        }
+    }
+
+    fn on_asset_reloaded(&mut self, context: &mut Context, path: &Path) {
+        for scene in self.scenes.iter_mut() {
+            scene.on_asset_reloaded(context, path);
+        }
+    }
+
+    fn shutdown(&mut self) {
+        for scene in self.scenes.iter_mut().rev() {
+            scene.shutdown();
+        }
+    }
+}
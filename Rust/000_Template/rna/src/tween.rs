@@ -0,0 +1,261 @@
+use raylib::prelude::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::easing::Easing;
+use super::math_ext::{clamp, lerp};
+
+/// A value that can be tweened; `t` may leave the range from zero to one for overshooting easings.
+pub trait Tweenable: Copy + 'static {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        lerp(from, to, t)
+    }
+}
+
+impl Tweenable for Vector2 {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        Vector2::new(lerp(from.x, to.x, t), lerp(from.y, to.y, t))
+    }
+}
+
+impl Tweenable for Color {
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        let channel =
+            |from: u8, to: u8| clamp(lerp(from as f32, to as f32, t).round(), 0.0, 255.0) as u8;
+
+        Color::new(
+            channel(from.r, to.r),
+            channel(from.g, to.g),
+            channel(from.b, to.b),
+            channel(from.a, to.a),
+        )
+    }
+}
+
+/// Moves a value from `from` to `to` over `duration` seconds, after an optional delay.
+///
+/// A tween plays once unless told to loop; with `yoyo`, every other loop plays backwards.
+#[derive(Clone, Debug)]
+pub struct Tween<T: Tweenable> {
+    from: T,
+    to: T,
+    duration: f32,
+    delay: f32,
+    easing: Easing,
+    loops: Option<u32>,
+    yoyo: bool,
+    elapsed: f32,
+}
+
+impl<T: Tweenable> Tween<T> {
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Tween {
+            from,
+            to,
+            duration,
+            delay: 0.0,
+            easing: Easing::Linear,
+            loops: Some(1),
+            yoyo: false,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Seconds to wait before the first loop starts.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Plays `count` times in total.
+    pub fn loops(mut self, count: u32) -> Self {
+        self.loops = Some(count.max(1));
+        self
+    }
+
+    /// Plays until it is removed.
+    pub fn forever(mut self) -> Self {
+        self.loops = None;
+        self
+    }
+
+    pub fn yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.is_finished() {
+            self.elapsed += delta_time;
+        }
+    }
+
+    /// Starts over from the beginning, including the delay.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        match self.loops {
+            Some(loops) => self.elapsed >= self.delay + self.duration.max(0.0) * loops as f32,
+            None => false,
+        }
+    }
+
+    /// Which loop is playing and how far into it, from zero to one.
+    fn position(&self) -> (u32, f32) {
+        let active = self.elapsed - self.delay;
+        let last = self.loops.map_or(u32::MAX, |loops| loops - 1);
+
+        if active <= 0.0 {
+            return (0, 0.0);
+        }
+        if self.duration <= 0.0 || self.is_finished() {
+            return (last, 1.0);
+        }
+
+        let played = active / self.duration;
+        (played.floor() as u32, played.fract())
+    }
+
+    /// How far along the current loop is, from zero to one and before easing.
+    pub fn progress(&self) -> f32 {
+        let (index, t) = self.position();
+
+        if self.yoyo && index % 2 == 1 {
+            1.0 - t
+        } else {
+            t
+        }
+    }
+
+    pub fn value(&self) -> T {
+        T::interpolate(self.from, self.to, self.easing.apply(self.progress()))
+    }
+}
+
+/// Refers to a tween inside `Tweens`.
+pub struct TweenHandle<T> {
+    id: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for TweenHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TweenHandle<T> {}
+
+impl<T> PartialEq for TweenHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for TweenHandle<T> {}
+
+impl<T> fmt::Debug for TweenHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TweenHandle({})", self.id)
+    }
+}
+
+/// Lets `Tweens` advance tweens of any type together.
+trait AnyTween {
+    fn update(&mut self, delta_time: f32);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Tweenable> AnyTween for Tween<T> {
+    fn update(&mut self, delta_time: f32) {
+        Tween::update(self, delta_time);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Tweens that are advanced by the `App` every frame, before `Core::update`.
+///
+/// Finished tweens keep their final value until they are removed, and can be `reset` to play again.
+pub struct Tweens {
+    tweens: HashMap<usize, Box<dyn AnyTween>>,
+    next_id: usize,
+}
+
+impl Tweens {
+    pub fn new() -> Self {
+        Tweens {
+            tweens: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn start<T: Tweenable>(&mut self, tween: Tween<T>) -> TweenHandle<T> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tweens.insert(id, Box::new(tween));
+
+        TweenHandle {
+            id,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn get<T: Tweenable>(&self, handle: TweenHandle<T>) -> Option<&Tween<T>> {
+        self.tweens.get(&handle.id)?.as_any().downcast_ref()
+    }
+
+    pub fn get_mut<T: Tweenable>(&mut self, handle: TweenHandle<T>) -> Option<&mut Tween<T>> {
+        self.tweens.get_mut(&handle.id)?.as_any_mut().downcast_mut()
+    }
+
+    pub fn value<T: Tweenable>(&self, handle: TweenHandle<T>) -> Option<T> {
+        self.get(handle).map(Tween::value)
+    }
+
+    pub fn is_finished<T: Tweenable>(&self, handle: TweenHandle<T>) -> bool {
+        self.get(handle).is_none_or(Tween::is_finished)
+    }
+
+    pub fn remove<T: Tweenable>(&mut self, handle: TweenHandle<T>) -> Option<Tween<T>> {
+        let tween = self.tweens.remove(&handle.id)?;
+        tween.into_any().downcast().ok().map(|tween| *tween)
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        for tween in self.tweens.values_mut() {
+            tween.update(delta_time);
+        }
+    }
+}
+
+impl Default for Tweens {
+    fn default() -> Self {
+        Tweens::new()
+    }
+}
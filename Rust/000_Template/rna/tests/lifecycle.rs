@@ -0,0 +1,58 @@
+use rna::*;
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+struct Watcher {
+    sizes: Vec<(i32, i32)>,
+    focus: Vec<bool>,
+    files: Vec<PathBuf>,
+    shutdowns: usize,
+}
+
+impl Core for Watcher {
+    fn initialize(&mut self, _: &mut Context) {}
+    fn update(&mut self, _: &mut Context) {}
+    fn draw(&self, _: &mut Frame) {}
+
+    fn on_resize(&mut self, width: i32, height: i32) {
+        self.sizes.push((width, height));
+    }
+    fn on_focus_changed(&mut self, focused: bool) {
+        self.focus.push(focused);
+    }
+    fn on_file_dropped(&mut self, path: &Path) {
+        self.files.push(path.to_path_buf());
+    }
+    fn shutdown(&mut self) {
+        self.shutdowns += 1;
+    }
+}
+
+#[test]
+fn window_changes_reach_the_hooks_once() {
+    let mut app = HeadlessApp::new(Watcher::default(), 320, 240);
+
+    app.backend_mut().set_screen_size(640, 480);
+    app.backend_mut().set_focused(false);
+    app.backend_mut().drop_file("world.txt");
+    app.run(3);
+    app.backend_mut().set_focused(true);
+    app.step();
+
+    assert_eq!(app.core().sizes, vec![(640, 480)]);
+    assert_eq!(app.core().focus, vec![false, true]);
+    assert_eq!(app.core().files, vec![PathBuf::from("world.txt")]);
+}
+
+#[test]
+fn shutdown_happens_once_the_window_closes() {
+    let mut app = HeadlessApp::new(Watcher::default(), 320, 240);
+
+    app.run(2);
+    assert_eq!(app.core().shutdowns, 0);
+
+    app.backend_mut().close();
+    app.run(2);
+    app.run(2);
+    assert_eq!(app.core().shutdowns, 1);
+}
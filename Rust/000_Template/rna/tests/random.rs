@@ -0,0 +1,142 @@
+use proptest::prelude::*;
+use raylib::prelude::*;
+use rna::*;
+
+#[test]
+fn the_same_seed_gives_the_same_sequence() {
+    let mut first = Random::new(42);
+    let mut second = Random::new(42);
+    let mut other = Random::new(43);
+
+    let sequence: Vec<u64> = (0..8).map(|_| first.next_u64()).collect();
+    assert_eq!(
+        sequence,
+        (0..8).map(|_| second.next_u64()).collect::<Vec<_>>()
+    );
+    assert_ne!(
+        sequence,
+        (0..8).map(|_| other.next_u64()).collect::<Vec<_>>()
+    );
+    assert_eq!(first.seed(), 42);
+}
+
+proptest! {
+    #[test]
+    fn ranges_stay_within_their_bounds(seed: u64, low: i64, high: i64, a: f32, b: f32) {
+        let mut random = Random::new(seed);
+
+        let value = random.range(low, high);
+        if low < high {
+            prop_assert!(low <= value && value < high);
+        } else {
+            prop_assert_eq!(value, low);
+        }
+
+        let (a, b) = (a.min(b), a.max(b));
+        prop_assume!(a.is_finite() && b.is_finite() && (b - a).is_finite() && a < b);
+        let value = random.range(a, b);
+        prop_assert!(a <= value && value < b);
+    }
+
+    #[test]
+    fn shuffling_keeps_every_item(seed: u64, mut items in prop::collection::vec(any::<u8>(), 0..64)) {
+        let mut shuffled = items.clone();
+        Random::new(seed).shuffle(&mut shuffled);
+
+        items.sort_unstable();
+        shuffled.sort_unstable();
+        prop_assert_eq!(items, shuffled);
+    }
+}
+
+#[test]
+fn small_ranges_are_unbiased() {
+    let mut random = Random::new(7);
+    let mut counts = [0; 6];
+
+    for _ in 0..60000 {
+        counts[random.range(0, 6)] += 1;
+    }
+
+    for count in counts.iter() {
+        assert!((9500..10500).contains(count), "{:?}", counts);
+    }
+}
+
+#[test]
+fn weighted_choices_follow_their_weights() {
+    let mut random = Random::new(3);
+    let items = [
+        ("never", 0.0),
+        ("rarely", 1.0),
+        ("often", 3.0),
+        ("broken", f64::NAN),
+    ];
+    let mut often = 0;
+
+    for _ in 0..4000 {
+        let (name, _) = random
+            .weighted_choose(&items, |(_, weight)| *weight)
+            .unwrap();
+        assert!(*name == "rarely" || *name == "often");
+        if *name == "often" {
+            often += 1;
+        }
+    }
+
+    assert!((2800..3200).contains(&often), "{}", often);
+    assert!(random
+        .weighted_choose(&items[..1], |(_, weight)| *weight)
+        .is_none());
+    assert!(random.choose::<u8>(&[]).is_none());
+}
+
+#[test]
+fn normal_samples_have_the_right_mean_and_spread() {
+    let mut random = Random::new(11);
+    let samples: Vec<f64> = (0..20000).map(|_| random.normal(5.0, 2.0)).collect();
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+
+    assert!(almost_equal(mean, 5.0, 0.05), "{}", mean);
+    assert!(
+        almost_equal(variance.sqrt(), 2.0, 0.05),
+        "{}",
+        variance.sqrt()
+    );
+}
+
+struct Dice {
+    rolls: Vec<u32>,
+}
+
+impl Core for Dice {
+    fn initialize(&mut self, _: &mut Context) {}
+    fn update(&mut self, context: &mut Context) {
+        let roll = context.random().range(1, 7);
+        self.rolls.push(roll);
+    }
+    fn draw(&self, _: &mut Frame) {}
+}
+
+#[test]
+fn replays_draw_the_same_numbers() {
+    let path = std::env::temp_dir().join(format!("rna-{}-dice.txt", std::process::id()));
+    let path = path.to_string_lossy();
+
+    let mut app = HeadlessApp::new(Dice { rolls: Vec::new() }, 320, 240)
+        .with_seed(1234)
+        .with_input_recording(&path);
+    app.backend_mut().press_key(KeyboardKey::KEY_SPACE);
+    app.run(20);
+    app.shutdown();
+
+    let mut replayed = HeadlessApp::new(Dice { rolls: Vec::new() }, 320, 240).with_seed(99);
+    replayed.replay(&path).unwrap();
+
+    assert_eq!(replayed.core().rolls, app.core().rolls);
+    assert!(app.core().rolls.iter().all(|roll| (1..7).contains(roll)));
+
+    std::fs::remove_file(path.as_ref()).unwrap();
+}
@@ -0,0 +1,118 @@
+use raylib::prelude::*;
+use rna::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+struct Scene {
+    name: &'static str,
+    log: Log,
+    overlay: bool,
+    next: Option<Transition>,
+}
+
+impl Scene {
+    fn new(name: &'static str, log: &Log) -> Self {
+        Scene {
+            name,
+            log: Rc::clone(log),
+            overlay: false,
+            next: None,
+        }
+    }
+
+    fn record(&self, event: &str) {
+        self.log
+            .borrow_mut()
+            .push(format!("{} {}", event, self.name));
+    }
+}
+
+impl Core for Scene {
+    fn initialize(&mut self, _: &mut Context) {}
+    fn update(&mut self, context: &mut Context) {
+        self.record("update");
+
+        if context.is_key_pressed(KeyboardKey::KEY_ENTER) && self.name == "menu" {
+            self.next = Some(Transition::Replace(Box::new(Scene::new("game", &self.log))));
+        } else if context.is_key_pressed(KeyboardKey::KEY_P) && self.name == "game" {
+            let mut pause = Scene::new("pause", &self.log);
+            pause.overlay = true;
+            self.next = Some(Transition::Push(Box::new(pause)));
+        } else if context.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
+            self.next = Some(Transition::Pop);
+        }
+    }
+    fn draw(&self, _: &mut Frame) {}
+
+    fn enter(&mut self, _: &mut Context) {
+        self.record("enter");
+    }
+    fn exit(&mut self, _: &mut Context) {
+        self.record("exit");
+    }
+    fn transition(&mut self) -> Option<Transition> {
+        self.next.take()
+    }
+    fn is_overlay(&self) -> bool {
+        self.overlay
+    }
+}
+
+fn press<C: Core>(app: &mut HeadlessApp<C>, key: KeyboardKey) {
+    app.backend_mut().press_key(key);
+    app.step();
+    app.backend_mut().release_key(key);
+}
+
+#[test]
+fn transitions_call_enter_and_exit() {
+    let log = Log::default();
+    let stack = SceneStack::new(Box::new(Scene::new("menu", &log)));
+    let mut app = HeadlessApp::new(stack, 320, 240);
+
+    press(&mut app, KeyboardKey::KEY_ENTER);
+    press(&mut app, KeyboardKey::KEY_P);
+    assert_eq!(app.core().len(), 2);
+
+    log.borrow_mut().clear();
+    app.step();
+    assert_eq!(*log.borrow(), vec!["update pause"]);
+
+    press(&mut app, KeyboardKey::KEY_ESCAPE);
+    press(&mut app, KeyboardKey::KEY_ESCAPE);
+    assert!(app.core().is_empty());
+}
+
+#[test]
+fn replacing_exits_before_entering() {
+    let log = Log::default();
+    let stack = SceneStack::new(Box::new(Scene::new("menu", &log)));
+    let mut app = HeadlessApp::new(stack, 320, 240);
+
+    press(&mut app, KeyboardKey::KEY_ENTER);
+
+    assert_eq!(
+        *log.borrow(),
+        vec!["enter menu", "update menu", "exit menu", "enter game"]
+    );
+}
+
+#[test]
+fn headless_apps_carry_out_the_transitions_of_their_core() {
+    let log = Log::default();
+    let mut app = HeadlessApp::new(Scene::new("menu", &log), 320, 240);
+
+    press(&mut app, KeyboardKey::KEY_ENTER);
+    assert_eq!(app.scenes().len(), 1);
+    assert_eq!(app.core().name, "menu");
+
+    press(&mut app, KeyboardKey::KEY_ESCAPE);
+    assert!(app.scenes().is_empty());
+
+    // Nothing is left to run, so the app stops right away.
+    log.borrow_mut().clear();
+    app.run(3);
+    assert!(log.borrow().is_empty());
+}
@@ -0,0 +1,125 @@
+use raylib::prelude::*;
+use rna::*;
+
+const EASINGS: [Easing; 31] = [
+    Easing::Linear,
+    Easing::SineIn,
+    Easing::SineOut,
+    Easing::SineInOut,
+    Easing::QuadIn,
+    Easing::QuadOut,
+    Easing::QuadInOut,
+    Easing::CubicIn,
+    Easing::CubicOut,
+    Easing::CubicInOut,
+    Easing::QuartIn,
+    Easing::QuartOut,
+    Easing::QuartInOut,
+    Easing::QuintIn,
+    Easing::QuintOut,
+    Easing::QuintInOut,
+    Easing::ExpoIn,
+    Easing::ExpoOut,
+    Easing::ExpoInOut,
+    Easing::CircIn,
+    Easing::CircOut,
+    Easing::CircInOut,
+    Easing::BackIn,
+    Easing::BackOut,
+    Easing::BackInOut,
+    Easing::ElasticIn,
+    Easing::ElasticOut,
+    Easing::ElasticInOut,
+    Easing::BounceIn,
+    Easing::BounceOut,
+    Easing::BounceInOut,
+];
+
+#[test]
+fn every_easing_starts_at_zero_and_ends_at_one() {
+    for easing in EASINGS.iter() {
+        assert!(almost_equal(easing.apply(0.0), 0.0, 1e-5), "{:?}", easing);
+        assert!(almost_equal(easing.apply(1.0), 1.0, 1e-5), "{:?}", easing);
+        assert_eq!(easing.apply(-1.0), easing.apply(0.0), "{:?}", easing);
+        assert_eq!(easing.apply(2.0), easing.apply(1.0), "{:?}", easing);
+    }
+
+    assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+    assert!(Easing::BackIn.apply(0.2) < 0.0);
+    assert!(Easing::ElasticOut.apply(0.2) > 1.0);
+}
+
+#[test]
+fn tweens_wait_loop_and_yoyo() {
+    let mut tween = Tween::new(0.0, 10.0, 1.0).delay(0.5).loops(2).yoyo(true);
+
+    tween.update(0.25);
+    assert_eq!(tween.value(), 0.0);
+    tween.update(0.75);
+    assert_eq!(tween.value(), 5.0);
+    tween.update(1.0);
+    assert_eq!(tween.value(), 5.0);
+    assert!(!tween.is_finished());
+    tween.update(0.5);
+    assert_eq!(tween.value(), 0.0);
+    assert!(tween.is_finished());
+
+    tween.reset();
+    assert!(!tween.is_finished());
+
+    let mut forever = Tween::new(0.0, 1.0, 1.0).forever();
+    forever.update(10.25);
+    assert_eq!(forever.progress(), 0.25);
+    assert!(!forever.is_finished());
+}
+
+#[test]
+fn colors_and_vectors_are_interpolated_per_component() {
+    let color = Color::interpolate(
+        Color::new(0, 100, 255, 255),
+        Color::new(255, 0, 255, 0),
+        0.5,
+    );
+    assert_eq!((color.r, color.g, color.b, color.a), (128, 50, 255, 128));
+
+    // Overshooting easings must not wrap the channels around.
+    let color = Color::interpolate(Color::BLACK, Color::WHITE, 1.5);
+    assert_eq!((color.r, color.g, color.b), (255, 255, 255));
+
+    let vector = Vector2::interpolate(Vector2::new(0.0, 10.0), Vector2::new(10.0, 0.0), 0.25);
+    assert_eq!((vector.x, vector.y), (2.5, 7.5));
+}
+
+struct Fader {
+    fade: Option<TweenHandle<f32>>,
+    values: Vec<f32>,
+}
+
+impl Core for Fader {
+    fn initialize(&mut self, context: &mut Context) {
+        self.fade = context
+            .tweens()
+            .map(|tweens| tweens.start(Tween::new(1.0, 0.0, 0.5)));
+    }
+    fn update(&mut self, context: &mut Context) {
+        let tweens = context.tweens().unwrap();
+        self.values.push(tweens.value(self.fade.unwrap()).unwrap());
+    }
+    fn draw(&self, _: &mut Frame) {}
+}
+
+#[test]
+fn the_app_advances_tweens_before_every_update() {
+    let mut app = HeadlessApp::new(
+        Fader {
+            fade: None,
+            values: Vec::new(),
+        },
+        320,
+        240,
+    );
+    app.backend_mut().set_frame_time(0.25);
+    app.run(3);
+
+    assert_eq!(app.core().values, vec![0.5, 0.0, 0.0]);
+}
@@ -6,10 +6,10 @@ pub struct Game {
 }
 
 impl Core for Game {
-    fn initialize(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {}
-    fn update(&mut self, rl: &mut RaylibHandle, _: &RaylibThread) {}
-    fn draw(&self, d: &mut RaylibDrawHandle, _: &RaylibThread) {
-        let mut d = d.begin_mode2D(self.camera);
+    fn initialize(&mut self, _context: &mut Context) {}
+    fn update(&mut self, _context: &mut Context) {}
+    fn draw(&self, frame: &mut Frame) {
+        let mut d = frame.begin_mode2D(self.camera);
         d.clear_background(Color::new(41, 173, 255, 255));
     }
 }
@@ -1,14 +1,20 @@
 mod game;
 use game::Game;
 use rna::*;
+use std::path::Path;
 
-fn main() {
-    let mut config = AppConfig::new();
+fn main() -> Result<(), RnaError> {
+    let mut config = AppConfig::builder()
+        .title("Template")
+        .size(640, 360)
+        .vsync(true);
 
-    config.title = "Template";
-    config.window_size = (640, 360);
-    config.vsync_enabled = true;
-    config.core = Some(Box::new(Game::new()));
+    // Window settings, as well as recording and replaying input, can be changed without recompiling.
+    if Path::new("window.txt").exists() {
+        config = config.load("window.txt")?;
+    }
 
-    App::build(config).run();
+    config.core(Game::new()).build()?.run();
+
+    Ok(())
 }
@@ -1,27 +1,29 @@
-use rand::prelude::*;
 use raylib::prelude::*;
 use rna::*;
 
-fn lerp(a: f64, b: f64, step: f64) -> f64 {
-    a + (b - a) * step
-}
+use crate::pause::Pause;
 
 pub struct Game {
+    input: InputMap,
     camera: Camera2D,
-    rng: ThreadRng,
+    seed: Option<u64>,
     vertices: Vec<Vector2>,
+    markers: Vec<TweenHandle<Vector2>>,
     points: Vec<Vector2>,
     last: Vector2,
     n: usize,
     r: f32,
     max: usize,
+    paused: bool,
 }
 
 impl Core for Game {
-    fn initialize(&mut self, rl: &mut RaylibHandle, _: &RaylibThread) {
-        let size = rl.get_screen_width() as f32;
+    fn initialize(&mut self, context: &mut Context) {
+        let size = context.screen_size().0 as f32;
         let increment = std::f32::consts::TAU / self.n as f32;
 
+        let center = Vector2::new(size * 0.5, size * 0.5);
+
         for i in 0..self.n {
             self.vertices.push(Vector2::new(
                 size * 0.5 + (increment * i as f32).cos() * size * 0.45,
@@ -29,24 +31,57 @@ impl Core for Game {
             ));
         }
 
-        let x = self.random_range(0, size as isize) as f32;
-        let y = self.random_range(0, size as isize) as f32;
+        // The vertices spring out of the center one after another before any points are placed.
+        if let Some(tweens) = context.tweens() {
+            for (i, vertex) in self.vertices.iter().enumerate() {
+                self.markers.push(
+                    tweens.start(
+                        Tween::new(center, *vertex, 0.6)
+                            .delay(i as f32 * 0.1)
+                            .easing(Easing::BackOut),
+                    ),
+                );
+            }
+        }
+
+        let random = context.random();
+        let x = random.range(0, size as i32) as f32;
+        let y = random.range(0, size as i32) as f32;
         self.last = Vector2::new(x, y);
     }
-    fn update(&mut self, _: &mut RaylibHandle, _: &RaylibThread) {
+    fn update(&mut self, context: &mut Context) {
+        if self.input.is_pressed(context, "pause") {
+            self.paused = true;
+        }
+
+        context.watch("points", self.points.len());
+
         if self.points.len() >= self.max {
             return;
         }
 
+        if let Some(tweens) = context.tweens() {
+            if !self
+                .markers
+                .iter()
+                .all(|marker| tweens.is_finished(*marker))
+            {
+                return;
+            }
+        }
+
         let iterations = 1000;
 
         for _ in 0..iterations {
-            let index = self.random_range(0, self.vertices.len() as isize);
-            let target = self.vertices[index as usize];
-
-            let x = lerp(self.last.x as f64, target.x as f64, self.r as f64) as f32;
-            let y = lerp(self.last.y as f64, target.y as f64, self.r as f64) as f32;
-            let result = Vector2::new(x, y);
+            let target = match context.random().choose(&self.vertices) {
+                Some(vertex) => *vertex,
+                None => return,
+            };
+
+            let result = Vector2::new(
+                lerp(self.last.x, target.x, self.r),
+                lerp(self.last.y, target.y, self.r),
+            );
 
             self.points.push(result);
             self.last = Vector2::new(result.x, result.y);
@@ -56,10 +91,20 @@ impl Core for Game {
             }
         }
     }
-    fn draw(&self, d: &mut RaylibDrawHandle, _: &RaylibThread) {
-        let mut d = d.begin_mode2D(self.camera);
+    fn draw(&self, frame: &mut Frame) {
+        let markers: Vec<Vector2> = self
+            .markers
+            .iter()
+            .filter_map(|marker| frame.tween(*marker))
+            .collect();
+
+        let mut d = frame.begin_mode2D(self.camera);
         d.clear_background(Color::WHITE);
 
+        for marker in markers.iter() {
+            d.draw_circle_v(*marker, 4.0, Color::new(255, 0, 77, 255));
+        }
+
         for point in self.points.iter() {
             d.draw_circle(
                 point.x as i32,
@@ -69,6 +114,14 @@ impl Core for Game {
             );
         }
     }
+
+    fn transition(&mut self) -> Option<Transition> {
+        if self.paused {
+            self.paused = false;
+            return Some(Transition::Push(Box::new(Pause::new(self.input.clone()))));
+        }
+        None
+    }
 }
 
 impl Game {
@@ -93,25 +146,33 @@ impl Game {
             .parse::<usize>()
             .unwrap_or(10000);
 
+        let seed = args.next().and_then(|seed| seed.parse::<u64>().ok());
+
+        let mut input = InputMap::new();
+        input.bind("pause", Binding::Key(KeyboardKey::KEY_P));
+
         Game {
+            input,
             camera: Camera2D {
                 zoom: 1.0,
                 target: Vector2::new(300.0, 300.0),
                 rotation: -90.0,
                 offset: Vector2::new(300.0, 300.0),
             },
-            rng: rand::thread_rng(),
+            seed,
             last: Vector2::new(0.0, 0.0),
             n,
             r: 1.0 - r,
             max,
+            paused: false,
             vertices: Vec::with_capacity(max),
+            markers: Vec::with_capacity(n),
             points: Vec::with_capacity(n),
         }
     }
 
-    fn random_range(&mut self, low: isize, high: isize) -> isize {
-        let r: f32 = self.rng.gen();
-        low + (r * (high - low) as f32).trunc() as isize
+    /// The seed given on the command line, if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
     }
 }
@@ -1,14 +1,30 @@
 mod game;
+mod pause;
 use game::Game;
 use rna::*;
+use std::path::Path;
 
-fn main() {
-    let mut config = AppConfig::new();
+fn main() -> Result<(), RnaError> {
+    let mut config = AppConfig::builder()
+        .title("Chaos Game")
+        .size(600, 600)
+        .vsync(true)
+        // The vertices are placed once, so the window scales the picture instead of changing its size.
+        .resizable(true)
+        .virtual_resolution(600, 600);
 
-    config.title = "Chaos Game";
-    config.window_size = (600, 600);
-    config.vsync_enabled = true;
-    config.core = Some(Box::new(Game::new(std::env::args())));
+    // Window settings, as well as recording and replaying input, can be changed without recompiling.
+    if Path::new("window.txt").exists() {
+        config = config.load("window.txt")?;
+    }
 
-    App::build(config).run();
+    let game = Game::new(std::env::args());
+    // A seed on the command line repeats an earlier run.
+    if let Some(seed) = game.seed() {
+        config = config.seed(seed);
+    }
+
+    config.core(game).build()?.run();
+
+    Ok(())
 }
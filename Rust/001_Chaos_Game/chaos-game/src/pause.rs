@@ -0,0 +1,56 @@
+use raylib::prelude::*;
+use rna::*;
+
+/// Dims the game beneath it and keeps it from updating until the pause action is triggered again.
+pub struct Pause {
+    input: InputMap,
+    resume: bool,
+}
+
+impl Core for Pause {
+    fn initialize(&mut self, _: &mut Context) {}
+    fn update(&mut self, context: &mut Context) {
+        if self.input.is_pressed(context, "pause") {
+            self.resume = true;
+        }
+    }
+    fn draw(&self, frame: &mut Frame) {
+        let (width, height) = frame.screen_size();
+        let font_size = 40;
+        let text_width = measure_text("Paused", font_size);
+
+        frame.rectangle(
+            Rectangle::new(0.0, 0.0, width as f32, height as f32),
+            Color::new(0, 0, 0, 155),
+        );
+        frame.text(
+            "Paused",
+            Vector2::new(
+                ((width - text_width) / 2) as f32,
+                ((height - font_size) / 2) as f32,
+            ),
+            font_size,
+            Color::WHITE,
+        );
+    }
+
+    fn transition(&mut self) -> Option<Transition> {
+        if self.resume {
+            self.resume = false;
+            return Some(Transition::Pop);
+        }
+        None
+    }
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
+
+impl Pause {
+    pub fn new(input: InputMap) -> Self {
+        Pause {
+            input,
+            resume: false,
+        }
+    }
+}
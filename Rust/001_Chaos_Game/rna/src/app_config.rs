@@ -1,23 +1,428 @@
+use raylib::prelude::*;
+use std::error::Error;
+use std::fs;
+
+use super::app::App;
+use super::capture::CaptureFormat;
 use super::core::Core;
+use super::error::RnaError;
+use super::input::Binding;
+use super::viewport::ScaleMode;
 
-pub struct AppConfig<'a> {
-    pub title: &'a str,
+pub struct AppConfig {
+    pub title: String,
     pub window_size: (i32, i32),
     pub vsync_enabled: bool,
     pub resizable: bool,
     pub borderless: bool,
+    /// Caps the frame rate; `None` leaves it unlimited, or up to vsync.
+    pub target_fps: Option<u32>,
+    pub fullscreen: bool,
+    /// Switches between fullscreen and windowed mode while the app runs.
+    pub fullscreen_key: Option<KeyboardKey>,
+    /// Asks for 4x multisample anti-aliasing.
+    pub msaa_4x: bool,
+    /// Scales the window along with the DPI of the monitor.
+    pub high_dpi: bool,
+    /// An image file shown as the icon of the window.
+    pub icon: Option<String>,
+    /// Where the top-left corner of the window is placed on the monitor.
+    pub position: Option<(i32, i32)>,
+    /// Limits how small a resizable window can be made.
+    pub min_size: Option<(i32, i32)>,
+    /// Limits how large a resizable window can be made.
+    pub max_size: Option<(i32, i32)>,
+    /// Keeps the app running while the window is minimized.
+    pub run_unfocused: bool,
+    /// Draws into a fixed logical resolution that is scaled to fit the window; input is reported in the same space.
+    pub virtual_resolution: Option<(i32, i32)>,
+    pub scale_mode: ScaleMode,
+    /// Fills the parts of the window the virtual resolution does not cover.
+    pub letterbox_color: Color,
+    /// When set, `Core::fixed_update` is called every `fixed_timestep` seconds regardless of the frame rate.
+    pub fixed_timestep: Option<f32>,
+    /// The most fixed updates a single frame may run while catching up; any remaining backlog is dropped.
+    pub max_fixed_updates: u32,
+    /// Saves the current frame as a PNG inside `capture_directory`.
+    pub screenshot_key: Option<KeyboardKey>,
+    /// Starts or stops recording into `capture_directory`.
+    pub record_key: Option<KeyboardKey>,
+    /// Starts recording as soon as the app runs, e.g. to capture a run from the command line.
+    pub record_on_start: bool,
+    pub capture_directory: String,
+    pub capture_format: CaptureFormat,
+    /// How many frames are captured per second of recording.
+    pub capture_frame_rate: u32,
+    /// Recording stops on its own after this many seconds.
+    pub capture_duration: Option<f32>,
+    /// Shows or hides the debug overlay.
+    pub debug_overlay_key: Option<KeyboardKey>,
+    /// Whether the debug overlay is visible from the start.
+    pub debug_overlay: bool,
+    /// How many frames the frame-time graph of the debug overlay covers.
+    pub frame_time_samples: usize,
+    /// How often, in seconds, files loaded through `Context::load` are checked for changes; `None` turns reloading off.
+    pub asset_poll_interval: Option<f32>,
+    /// Records the input of every frame into this file, to be replayed with `replay_input`.
+    pub record_input: Option<String>,
+    /// Replays input recorded with `record_input` before handing control back to the real input.
+    /// Nothing is recorded while replaying.
+    pub replay_input: Option<String>,
+    /// Seeds `Context::random`; `None` picks a different seed every run. The seed is printed at startup
+    /// so that an interesting run can be repeated.
+    pub seed: Option<u64>,
     pub core: Option<Box<dyn Core>>,
 }
 
-impl<'a> AppConfig<'a> {
+impl AppConfig {
     pub fn new() -> Self {
         AppConfig {
-            title: "Application",
+            title: String::from("Application"),
             window_size: (640, 360),
             vsync_enabled: false,
             resizable: false,
             borderless: false,
+            target_fps: None,
+            fullscreen: false,
+            fullscreen_key: Some(KeyboardKey::KEY_F11),
+            msaa_4x: false,
+            high_dpi: false,
+            icon: None,
+            position: None,
+            min_size: None,
+            max_size: None,
+            run_unfocused: false,
+            virtual_resolution: None,
+            scale_mode: ScaleMode::Fit,
+            letterbox_color: Color::BLACK,
+            fixed_timestep: None,
+            max_fixed_updates: 5,
+            screenshot_key: Some(KeyboardKey::KEY_F9),
+            record_key: Some(KeyboardKey::KEY_F10),
+            record_on_start: false,
+            capture_directory: String::from("captures"),
+            capture_format: CaptureFormat::Gif,
+            capture_frame_rate: 30,
+            capture_duration: Some(10.0),
+            debug_overlay_key: Some(KeyboardKey::KEY_F3),
+            debug_overlay: false,
+            frame_time_samples: 120,
+            asset_poll_interval: Some(0.5),
+            record_input: None,
+            replay_input: None,
+            seed: None,
             core: None,
         }
     }
+    pub fn builder() -> AppConfigBuilder {
+        AppConfigBuilder {
+            config: AppConfig::new(),
+        }
+    }
+
+    /// Overrides settings with the ones in a file of `setting value` lines, e.g. `size 1280,720`
+    /// or `record_input session.txt`.
+    pub fn load(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (setting, value) = line.split_once(char::is_whitespace).ok_or(format!(
+                "line {}: expected a setting followed by a value",
+                number + 1
+            ))?;
+            let value = value.trim();
+            let invalid = || {
+                format!(
+                    "line {}: invalid value '{}' for '{}'",
+                    number + 1,
+                    value,
+                    setting
+                )
+            };
+
+            match setting {
+                "title" => self.title = String::from(value),
+                "size" => self.window_size = parse_size(value).ok_or_else(invalid)?,
+                "vsync" => self.vsync_enabled = value.parse().map_err(|_| invalid())?,
+                "resizable" => self.resizable = value.parse().map_err(|_| invalid())?,
+                "borderless" => self.borderless = value.parse().map_err(|_| invalid())?,
+                "target_fps" => {
+                    self.target_fps = match value {
+                        "none" => None,
+                        _ => Some(value.parse().map_err(|_| invalid())?),
+                    }
+                }
+                "fullscreen" => self.fullscreen = value.parse().map_err(|_| invalid())?,
+                "fullscreen_key" => self.fullscreen_key = parse_key(value).ok_or_else(invalid)?,
+                "msaa_4x" => self.msaa_4x = value.parse().map_err(|_| invalid())?,
+                "high_dpi" => self.high_dpi = value.parse().map_err(|_| invalid())?,
+                "icon" => self.icon = Some(String::from(value)),
+                "position" => self.position = Some(parse_size(value).ok_or_else(invalid)?),
+                "min_size" => self.min_size = Some(parse_size(value).ok_or_else(invalid)?),
+                "max_size" => self.max_size = Some(parse_size(value).ok_or_else(invalid)?),
+                "run_unfocused" => self.run_unfocused = value.parse().map_err(|_| invalid())?,
+                "virtual_resolution" => {
+                    self.virtual_resolution = match value {
+                        "none" => None,
+                        _ => Some(parse_size(value).ok_or_else(invalid)?),
+                    }
+                }
+                "record_input" => {
+                    self.record_input = match value {
+                        "none" => None,
+                        _ => Some(String::from(value)),
+                    }
+                }
+                "replay_input" => {
+                    self.replay_input = match value {
+                        "none" => None,
+                        _ => Some(String::from(value)),
+                    }
+                }
+                "seed" => {
+                    self.seed = match value {
+                        "none" => None,
+                        _ => Some(value.parse().map_err(|_| invalid())?),
+                    }
+                }
+                "scale_mode" => {
+                    self.scale_mode = match value {
+                        "integer" => ScaleMode::Integer,
+                        "fit" => ScaleMode::Fit,
+                        _ => return Err(invalid().into()),
+                    }
+                }
+                _ => {
+                    return Err(
+                        format!("line {}: unknown setting '{}'", number + 1, setting).into(),
+                    )
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_size(value: &str) -> Option<(i32, i32)> {
+    let (width, height) = value.split_once(',')?;
+
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+/// Parses a key such as `F11`, or `none` to leave the key unbound.
+fn parse_key(value: &str) -> Option<Option<KeyboardKey>> {
+    if value.eq_ignore_ascii_case("none") {
+        return Some(None);
+    }
+
+    match Binding::from_name(value)? {
+        Binding::Key(key) => Some(Some(key)),
+        _ => None,
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig::new()
+    }
+}
+
+/// Builds an `App` one setting at a time, e.g. `AppConfig::builder().title("Game").core(game).build()?`.
+pub struct AppConfigBuilder {
+    config: AppConfig,
+}
+
+impl AppConfigBuilder {
+    pub fn title(mut self, title: &str) -> Self {
+        self.config.title = String::from(title);
+        self
+    }
+
+    pub fn size(mut self, width: i32, height: i32) -> Self {
+        self.config.window_size = (width, height);
+        self
+    }
+
+    pub fn vsync(mut self, enabled: bool) -> Self {
+        self.config.vsync_enabled = enabled;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.config.resizable = resizable;
+        self
+    }
+
+    pub fn borderless(mut self, borderless: bool) -> Self {
+        self.config.borderless = borderless;
+        self
+    }
+
+    pub fn target_fps(mut self, fps: u32) -> Self {
+        self.config.target_fps = Some(fps);
+        self
+    }
+
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.config.fullscreen = fullscreen;
+        self
+    }
+
+    pub fn fullscreen_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.config.fullscreen_key = key;
+        self
+    }
+
+    pub fn msaa_4x(mut self, enabled: bool) -> Self {
+        self.config.msaa_4x = enabled;
+        self
+    }
+
+    pub fn high_dpi(mut self, enabled: bool) -> Self {
+        self.config.high_dpi = enabled;
+        self
+    }
+
+    pub fn icon(mut self, path: &str) -> Self {
+        self.config.icon = Some(String::from(path));
+        self
+    }
+
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.config.position = Some((x, y));
+        self
+    }
+
+    pub fn min_size(mut self, width: i32, height: i32) -> Self {
+        self.config.min_size = Some((width, height));
+        self
+    }
+
+    pub fn max_size(mut self, width: i32, height: i32) -> Self {
+        self.config.max_size = Some((width, height));
+        self
+    }
+
+    pub fn run_unfocused(mut self, enabled: bool) -> Self {
+        self.config.run_unfocused = enabled;
+        self
+    }
+
+    pub fn virtual_resolution(mut self, width: i32, height: i32) -> Self {
+        self.config.virtual_resolution = Some((width, height));
+        self
+    }
+
+    pub fn scale_mode(mut self, mode: ScaleMode) -> Self {
+        self.config.scale_mode = mode;
+        self
+    }
+
+    pub fn letterbox_color(mut self, color: Color) -> Self {
+        self.config.letterbox_color = color;
+        self
+    }
+
+    /// Applies the settings stored in `path`; see `AppConfig::load`.
+    pub fn load(mut self, path: &str) -> Result<Self, RnaError> {
+        self.config
+            .load(path)
+            .map_err(|error| RnaError::InvalidConfig(String::from(path), error.to_string()))?;
+        Ok(self)
+    }
+
+    pub fn fixed_timestep(mut self, timestep: f32) -> Self {
+        self.config.fixed_timestep = Some(timestep);
+        self
+    }
+
+    pub fn max_fixed_updates(mut self, max_updates: u32) -> Self {
+        self.config.max_fixed_updates = max_updates;
+        self
+    }
+
+    pub fn screenshot_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.config.screenshot_key = key;
+        self
+    }
+
+    pub fn record_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.config.record_key = key;
+        self
+    }
+
+    pub fn record_on_start(mut self, record: bool) -> Self {
+        self.config.record_on_start = record;
+        self
+    }
+
+    pub fn capture_directory(mut self, directory: &str) -> Self {
+        self.config.capture_directory = String::from(directory);
+        self
+    }
+
+    pub fn capture_format(mut self, format: CaptureFormat) -> Self {
+        self.config.capture_format = format;
+        self
+    }
+
+    pub fn capture_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.config.capture_frame_rate = frame_rate;
+        self
+    }
+
+    pub fn capture_duration(mut self, duration: Option<f32>) -> Self {
+        self.config.capture_duration = duration;
+        self
+    }
+
+    pub fn debug_overlay_key(mut self, key: Option<KeyboardKey>) -> Self {
+        self.config.debug_overlay_key = key;
+        self
+    }
+
+    pub fn debug_overlay(mut self, visible: bool) -> Self {
+        self.config.debug_overlay = visible;
+        self
+    }
+
+    pub fn frame_time_samples(mut self, samples: usize) -> Self {
+        self.config.frame_time_samples = samples;
+        self
+    }
+
+    pub fn asset_poll_interval(mut self, interval: Option<f32>) -> Self {
+        self.config.asset_poll_interval = interval;
+        self
+    }
+
+    pub fn record_input(mut self, path: &str) -> Self {
+        self.config.record_input = Some(String::from(path));
+        self
+    }
+
+    pub fn replay_input(mut self, path: &str) -> Self {
+        self.config.replay_input = Some(String::from(path));
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn core<C: Core + 'static>(mut self, core: C) -> Self {
+        self.config.core = Some(Box::new(core));
+        self
+    }
+
+    pub fn build(self) -> Result<App, RnaError> {
+        App::build(self.config)
+    }
 }
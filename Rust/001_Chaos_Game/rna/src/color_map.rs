@@ -0,0 +1,217 @@
+use raylib::prelude::*;
+
+use super::math_ext::remap_range;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColorMap {
+    /// Red for negative values, black around zero and green for positive values.
+    Classic,
+    /// Blue for negative values, white around zero and red for positive values.
+    Diverging,
+    Viridis,
+    Grayscale,
+    /// A sequential map that stays readable for the most common forms of color blindness.
+    Cividis,
+}
+
+/// How values are spread across a color map.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scaling {
+    /// Negative and positive values share the same scale, so equal magnitudes get equally strong colors.
+    Symmetric,
+    /// Negative values are stretched over the lower half of the map and positive values over the upper half.
+    Independent,
+}
+
+const CLASSIC: [(u8, u8, u8); 3] = [(255, 0, 0), (0, 0, 0), (0, 255, 0)];
+
+const DIVERGING: [(u8, u8, u8); 7] = [
+    (33, 102, 172),
+    (103, 169, 207),
+    (209, 229, 240),
+    (247, 247, 247),
+    (253, 219, 199),
+    (239, 138, 98),
+    (178, 24, 43),
+];
+
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 44, 122),
+    (59, 81, 139),
+    (44, 113, 142),
+    (33, 144, 141),
+    (39, 173, 129),
+    (92, 200, 99),
+    (170, 220, 50),
+    (253, 231, 37),
+];
+
+const GRAYSCALE: [(u8, u8, u8); 2] = [(0, 0, 0), (255, 255, 255)];
+
+const CIVIDIS: [(u8, u8, u8); 5] = [
+    (0, 32, 77),
+    (64, 76, 107),
+    (124, 123, 120),
+    (188, 175, 111),
+    (255, 234, 70),
+];
+
+const COLOR_MAPS: [ColorMap; 5] = [
+    ColorMap::Classic,
+    ColorMap::Diverging,
+    ColorMap::Viridis,
+    ColorMap::Grayscale,
+    ColorMap::Cividis,
+];
+
+impl ColorMap {
+    pub fn from_name(name: &str) -> Option<Self> {
+        COLOR_MAPS
+            .iter()
+            .find(|color_map| color_map.name().eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorMap::Classic => "Classic",
+            ColorMap::Diverging => "Diverging",
+            ColorMap::Viridis => "Viridis",
+            ColorMap::Grayscale => "Grayscale",
+            ColorMap::Cividis => "Cividis",
+        }
+    }
+
+    /// Returns the color map that follows this one, wrapping around after the last.
+    pub fn next(&self) -> Self {
+        let index = COLOR_MAPS
+            .iter()
+            .position(|color_map| color_map == self)
+            .unwrap_or(0);
+
+        COLOR_MAPS[(index + 1) % COLOR_MAPS.len()]
+    }
+
+    fn stops(&self) -> &'static [(u8, u8, u8)] {
+        match self {
+            ColorMap::Classic => &CLASSIC,
+            ColorMap::Diverging => &DIVERGING,
+            ColorMap::Viridis => &VIRIDIS,
+            ColorMap::Grayscale => &GRAYSCALE,
+            ColorMap::Cividis => &CIVIDIS,
+        }
+    }
+
+    /// Samples the color map at `t`, where zero is the low end and one is the high end.
+    pub fn sample(&self, t: f64) -> Color {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.5 } else { t.clamp(0.0, 1.0) };
+
+        let position = t * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let step = position - index as f64;
+
+        let (a, b) = (stops[index], stops[index + 1]);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * step).round() as u8;
+
+        Color::new(channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2), 255)
+    }
+
+    /// Maps `value` onto the color map, placing zero in the middle.
+    pub fn color(&self, value: f64, min: f64, max: f64, scaling: Scaling) -> Color {
+        self.sample(normalize(value, min, max, scaling))
+    }
+
+    /// Draws a vertical legend bar with the high end at the top and tick values to its left.
+    pub fn draw_legend<D: RaylibDraw>(
+        &self,
+        d: &mut D,
+        bounds: Rectangle,
+        min: f64,
+        max: f64,
+        scaling: Scaling,
+    ) {
+        let height = bounds.height as i32;
+
+        for i in 0..height {
+            let t = 1.0 - i as f64 / (height - 1).max(1) as f64;
+            d.draw_rectangle(
+                bounds.x as i32,
+                bounds.y as i32 + i,
+                bounds.width as i32,
+                1,
+                self.sample(t),
+            );
+        }
+
+        d.draw_rectangle_lines_ex(bounds, 1, Color::new(255, 255, 255, 155));
+
+        let ticks = 4;
+        let font_size = 10;
+        for i in 0..=ticks {
+            let t = i as f64 / ticks as f64;
+            let y = bounds.y as i32 + ((1.0 - t) * (height - 1) as f64) as i32;
+            let text = format!("{:.2}", denormalize(t, min, max, scaling));
+            let width = measure_text(&text, font_size);
+
+            d.draw_line(
+                bounds.x as i32 - 4,
+                y,
+                bounds.x as i32,
+                y,
+                Color::new(255, 255, 255, 255),
+            );
+            d.draw_text(
+                &text,
+                bounds.x as i32 - 6 - width,
+                y - font_size / 2,
+                font_size,
+                Color::new(255, 255, 255, 255),
+            );
+        }
+    }
+}
+
+/// Converts `value` into a position on a color map, where zero always lands on one half.
+fn normalize(value: f64, min: f64, max: f64, scaling: Scaling) -> f64 {
+    match scaling {
+        Scaling::Symmetric => {
+            let magnitude = min.abs().max(max.abs());
+            if magnitude == 0.0 {
+                return 0.5;
+            }
+            remap_range(value, -magnitude, magnitude, 0.0, 1.0)
+        }
+        Scaling::Independent => {
+            if value < 0.0 {
+                if min >= 0.0 {
+                    return 0.5;
+                }
+                remap_range(value, min, 0.0, 0.0, 0.5)
+            } else {
+                if max <= 0.0 {
+                    return 0.5;
+                }
+                remap_range(value, 0.0, max, 0.5, 1.0)
+            }
+        }
+    }
+}
+
+/// The inverse of `normalize`; turns a position on a color map back into a value.
+fn denormalize(t: f64, min: f64, max: f64, scaling: Scaling) -> f64 {
+    match scaling {
+        Scaling::Symmetric => {
+            let magnitude = min.abs().max(max.abs());
+            remap_range(t, 0.0, 1.0, -magnitude, magnitude)
+        }
+        Scaling::Independent => {
+            if t < 0.5 {
+                remap_range(t, 0.0, 0.5, min.min(0.0), 0.0)
+            } else {
+                remap_range(t, 0.5, 1.0, 0.0, max.max(0.0))
+            }
+        }
+    }
+}
@@ -1,7 +1,37 @@
-use raylib::prelude::*;
+use std::path::Path;
+
+use super::context::{Context, Frame};
+use super::scene::Transition;
 
 pub trait Core {
-    fn initialize(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread);
-    fn update(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread);
-    fn draw(&self, d: &mut RaylibDrawHandle, thread: &RaylibThread);
+    fn initialize(&mut self, context: &mut Context);
+    fn update(&mut self, context: &mut Context);
+    /// Called zero or more times per frame at the rate set by `AppConfig::fixed_timestep`.
+    fn fixed_update(&mut self, _context: &mut Context) {}
+    fn draw(&self, frame: &mut Frame);
+
+    /// Called right after `initialize` when this scene is put on the scene stack.
+    fn enter(&mut self, _context: &mut Context) {}
+    /// Called when this scene is popped or replaced.
+    fn exit(&mut self, _context: &mut Context) {}
+    /// Polled after every update while this scene is on top of the scene stack.
+    fn transition(&mut self) -> Option<Transition> {
+        None
+    }
+    /// Modal scenes keep the scenes beneath them from updating.
+    fn is_modal(&self) -> bool {
+        true
+    }
+    /// Overlays are drawn on top of the scenes beneath them instead of hiding them.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    fn on_resize(&mut self, _width: i32, _height: i32) {}
+    fn on_focus_changed(&mut self, _focused: bool) {}
+    fn on_file_dropped(&mut self, _path: &Path) {}
+    /// Called after a file loaded through `Context::load` changed on disk and was reloaded.
+    fn on_asset_reloaded(&mut self, _context: &mut Context, _path: &Path) {}
+    /// Called once when the window is about to close, e.g. to save state.
+    fn shutdown(&mut self) {}
 }
@@ -0,0 +1,175 @@
+use raylib::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Named values a `Core` wants to keep an eye on, registered every frame through `Context::watch`.
+pub(crate) struct Watches {
+    enabled: bool,
+    values: Vec<(String, String)>,
+}
+
+impl Watches {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Watches {
+            enabled,
+            values: Vec::new(),
+        }
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Values are only formatted while someone is looking at them.
+    pub(crate) fn set(&mut self, name: &str, value: &dyn Display) {
+        if !self.enabled {
+            return;
+        }
+
+        let value = value.to_string();
+        match self.values.iter_mut().find(|(other, _)| other == name) {
+            Some((_, current)) => *current = value,
+            None => self.values.push((String::from(name), value)),
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Frame timings and watch values drawn on top of the app.
+pub(crate) struct DebugOverlay {
+    visible: bool,
+    frame_times: VecDeque<f32>,
+    samples: usize,
+    update_time: Duration,
+    draw_time: Duration,
+}
+
+impl DebugOverlay {
+    pub(crate) fn new(visible: bool, samples: usize) -> Self {
+        DebugOverlay {
+            visible,
+            frame_times: VecDeque::with_capacity(samples),
+            samples: samples.max(2),
+            update_time: Duration::default(),
+            draw_time: Duration::default(),
+        }
+    }
+
+    /// Watches are only kept up to date while the overlay shows them.
+    pub(crate) fn toggle(&mut self, watches: &mut Watches) {
+        self.visible = !self.visible;
+        watches.set_enabled(self.visible);
+    }
+
+    pub(crate) fn record(&mut self, frame_time: f32, update_time: Duration, draw_time: Duration) {
+        if self.frame_times.len() == self.samples {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+
+        self.update_time = update_time;
+        self.draw_time = draw_time;
+    }
+
+    pub(crate) fn draw<D: RaylibDraw>(
+        &self,
+        d: &mut D,
+        watches: &Watches,
+        screen_width: i32,
+        fps: u32,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let font_size = 10;
+        let line_height = 12;
+        let width = 220;
+        let graph_height = 40;
+        let x = screen_width - width - 10;
+        let height =
+            20 + line_height * 3 + graph_height + line_height * watches.values.len() as i32;
+
+        let text = Color::new(255, 255, 255, 255);
+        d.draw_rectangle(x, 10, width, height, Color::new(0, 0, 0, 185));
+
+        let frame_time = self.frame_times.back().copied().unwrap_or(0.0);
+        let lines = [
+            format!("FPS {}", fps),
+            format!("Frame {:.2} ms", frame_time * 1000.0),
+            format!(
+                "Update {:.2} ms  Draw {:.2} ms",
+                self.update_time.as_secs_f32() * 1000.0,
+                self.draw_time.as_secs_f32() * 1000.0
+            ),
+        ];
+
+        let mut y = 15;
+        for line in lines.iter() {
+            d.draw_text(line, x + 5, y, font_size, text);
+            y += line_height;
+        }
+
+        self.draw_graph(
+            d,
+            Rectangle::new(
+                (x + 5) as f32,
+                y as f32,
+                (width - 10) as f32,
+                graph_height as f32,
+            ),
+        );
+        y += graph_height + 5;
+
+        for (name, value) in watches.values.iter() {
+            d.draw_text(&format!("{} {}", name, value), x + 5, y, font_size, text);
+            y += line_height;
+        }
+    }
+
+    /// Draws one bar per frame, scaled so that at least two 60 Hz frames fit.
+    fn draw_graph<D: RaylibDraw>(&self, d: &mut D, bounds: Rectangle) {
+        let target = 1. / 60.;
+        let ceiling = self
+            .frame_times
+            .iter()
+            .copied()
+            .fold(target * 2.0, f32::max);
+        let bar_width = bounds.width / self.samples as f32;
+
+        d.draw_rectangle_lines_ex(bounds, 1, Color::new(255, 255, 255, 55));
+
+        for (i, frame_time) in self.frame_times.iter().enumerate() {
+            let height = bounds.height * frame_time / ceiling;
+            let color = if *frame_time > target * 1.5 {
+                Color::new(255, 0, 77, 255)
+            } else {
+                Color::new(0, 228, 54, 255)
+            };
+
+            d.draw_rectangle_rec(
+                Rectangle::new(
+                    bounds.x + i as f32 * bar_width,
+                    bounds.y + bounds.height - height,
+                    bar_width.max(1.0),
+                    height,
+                ),
+                color,
+            );
+        }
+
+        let y = bounds.y + bounds.height - bounds.height * target / ceiling;
+        d.draw_line_v(
+            Vector2::new(bounds.x, y),
+            Vector2::new(bounds.x + bounds.width, y),
+            Color::new(255, 255, 255, 155),
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::f32::consts::PI;
+
+use super::math_ext::clamp;
+
+/// Robert Penner's easing curves, which shape how a `Tween` moves from start to end.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+const BACK: f32 = 1.70158;
+const BACK_IN_OUT: f32 = BACK * 1.525;
+const ELASTIC: f32 = 2.0 * PI / 3.0;
+const ELASTIC_IN_OUT: f32 = 2.0 * PI / 4.5;
+
+fn power_in(t: f32, power: i32) -> f32 {
+    t.powi(power)
+}
+
+fn power_out(t: f32, power: i32) -> f32 {
+    1.0 - (1.0 - t).powi(power)
+}
+
+fn power_in_out(t: f32, power: i32) -> f32 {
+    if t < 0.5 {
+        2f32.powi(power - 1) * t.powi(power)
+    } else {
+        1.0 - (2.0 - 2.0 * t).powi(power) * 0.5
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+impl Easing {
+    /// Eases `t`, which is clamped between zero and one; the result is zero at the start and one at the end,
+    /// but back and elastic curves overshoot in between.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = clamp(t, 0.0, 1.0);
+
+        // The exponential curves never quite reach their ends on their own.
+        match self {
+            Easing::ExpoIn
+            | Easing::ExpoOut
+            | Easing::ExpoInOut
+            | Easing::ElasticIn
+            | Easing::ElasticOut
+            | Easing::ElasticInOut
+                if t == 0.0 || t == 1.0 =>
+            {
+                return t
+            }
+            _ => (),
+        }
+
+        match self {
+            Easing::Linear => t,
+            Easing::SineIn => 1.0 - (t * PI * 0.5).cos(),
+            Easing::SineOut => (t * PI * 0.5).sin(),
+            Easing::SineInOut => (1.0 - (t * PI).cos()) * 0.5,
+            Easing::QuadIn => power_in(t, 2),
+            Easing::QuadOut => power_out(t, 2),
+            Easing::QuadInOut => power_in_out(t, 2),
+            Easing::CubicIn => power_in(t, 3),
+            Easing::CubicOut => power_out(t, 3),
+            Easing::CubicInOut => power_in_out(t, 3),
+            Easing::QuartIn => power_in(t, 4),
+            Easing::QuartOut => power_out(t, 4),
+            Easing::QuartInOut => power_in_out(t, 4),
+            Easing::QuintIn => power_in(t, 5),
+            Easing::QuintOut => power_out(t, 5),
+            Easing::QuintInOut => power_in_out(t, 5),
+            Easing::ExpoIn => 2f32.powf(10.0 * t - 10.0),
+            Easing::ExpoOut => 1.0 - 2f32.powf(-10.0 * t),
+            Easing::ExpoInOut => {
+                if t < 0.5 {
+                    2f32.powf(20.0 * t - 10.0) * 0.5
+                } else {
+                    (2.0 - 2f32.powf(10.0 - 20.0 * t)) * 0.5
+                }
+            }
+            Easing::CircIn => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::CircOut => (1.0 - (t - 1.0) * (t - 1.0)).sqrt(),
+            Easing::CircInOut => {
+                if t < 0.5 {
+                    (1.0 - (1.0 - 4.0 * t * t).sqrt()) * 0.5
+                } else {
+                    ((1.0 - (2.0 - 2.0 * t).powi(2)).sqrt() + 1.0) * 0.5
+                }
+            }
+            Easing::BackIn => (BACK + 1.0) * t * t * t - BACK * t * t,
+            Easing::BackOut => {
+                let t = t - 1.0;
+                1.0 + (BACK + 1.0) * t * t * t + BACK * t * t
+            }
+            Easing::BackInOut => {
+                let t = t * 2.0;
+                if t < 1.0 {
+                    t * t * ((BACK_IN_OUT + 1.0) * t - BACK_IN_OUT) * 0.5
+                } else {
+                    let t = t - 2.0;
+                    (t * t * ((BACK_IN_OUT + 1.0) * t + BACK_IN_OUT) + 2.0) * 0.5
+                }
+            }
+            Easing::ElasticIn => {
+                -(2f32.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * ELASTIC).sin()
+            }
+            Easing::ElasticOut => 2f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * ELASTIC).sin() + 1.0,
+            Easing::ElasticInOut => {
+                let wave = ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin();
+                if t < 0.5 {
+                    -(2f32.powf(20.0 * t - 10.0)) * wave * 0.5
+                } else {
+                    2f32.powf(10.0 - 20.0 * t) * wave * 0.5 + 1.0
+                }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 {
+                    (1.0 - bounce_out(1.0 - 2.0 * t)) * 0.5
+                } else {
+                    (1.0 + bounce_out(2.0 * t - 1.0)) * 0.5
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::cell::{Ref, RefCell, RefMut};
+use std::error::Error;
+use std::path::Path;
+use std::rc::Rc;
+
+use super::assets::Assets;
+use super::backend::{Backend, HeadlessBackend};
+use super::context::{Context, Frame};
+use super::core::Core;
+use super::debug::Watches;
+use super::random::Random;
+use super::replay::Replay;
+use super::runner::{Runner, Services};
+use super::scene::{SceneStack, Transition};
+use super::timestep::FixedTimestep;
+use super::tween::Tweens;
+
+/// Runs a `Core` without a window, e.g. from `cargo test` on a machine without a display.
+///
+/// Only `initialize`, `update` and `fixed_update` are called; nothing is ever drawn.
+/// Loaded assets are checked for changes every frame.
+///
+/// Like `App`, the core is the first scene of a `SceneStack`, so its transitions are carried out and the app stops
+/// once the last scene is gone. The core stays reachable through `core` even after it left the stack.
+pub struct HeadlessApp<C: Core + 'static> {
+    backend: HeadlessBackend,
+    core: Rc<RefCell<C>>,
+    scenes: SceneStack,
+    runner: Runner,
+    initialized: bool,
+    shut_down: bool,
+}
+
+impl<C: Core + 'static> HeadlessApp<C> {
+    pub fn new(core: C, screen_width: i32, screen_height: i32) -> Self {
+        let core = Rc::new(RefCell::new(core));
+
+        HeadlessApp {
+            backend: HeadlessBackend::new(screen_width, screen_height),
+            scenes: SceneStack::new(Box::new(SharedCore(Rc::clone(&core)))),
+            core,
+            runner: Runner::new(
+                Services {
+                    watches: Watches::new(true),
+                    assets: Assets::new(Some(0.0)),
+                    replay: Replay::Off,
+                    tweens: Tweens::new(),
+                    random: Random::new(0),
+                },
+                FixedTimestep::new(None, 5),
+            ),
+            initialized: false,
+            shut_down: false,
+        }
+    }
+
+    /// Mirrors `AppConfig::fixed_timestep` and `AppConfig::max_fixed_updates`.
+    pub fn with_fixed_timestep(mut self, timestep: f32, max_updates: u32) -> Self {
+        self.runner
+            .set_timestep(FixedTimestep::new(Some(timestep), max_updates));
+        self
+    }
+
+    /// Mirrors `AppConfig::seed`; without it the seed is zero.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.runner.services.random = Random::new(seed);
+        self
+    }
+
+    /// Mirrors `AppConfig::record_input`; the recording is saved on `shutdown`.
+    pub fn with_input_recording(mut self, path: &str) -> Self {
+        self.runner.services.replay = Replay::record(path);
+        self
+    }
+
+    pub fn core(&self) -> Ref<'_, C> {
+        self.core.borrow()
+    }
+
+    pub fn core_mut(&mut self) -> RefMut<'_, C> {
+        self.core.borrow_mut()
+    }
+
+    /// The scenes that are still running; empty once the last one was popped.
+    pub fn scenes(&self) -> &SceneStack {
+        &self.scenes
+    }
+
+    /// Simulated input is fed to the core through the backend before calling `step`.
+    pub fn backend_mut(&mut self) -> &mut HeadlessBackend {
+        &mut self.backend
+    }
+
+    /// The latest value the core registered through `Context::watch`.
+    pub fn watch(&self, name: &str) -> Option<&str> {
+        self.runner.services.watches.get(name)
+    }
+
+    /// Runs a single frame and returns the interpolation alpha that would have been passed to `draw`.
+    pub fn step(&mut self) -> f32 {
+        if let Some(input) = self.runner.services.replay.next_frame(&mut self.backend) {
+            self.backend.input = input;
+        }
+
+        if !self.initialized {
+            self.runner.initialize(&mut self.scenes, &mut self.backend);
+            self.initialized = true;
+        }
+
+        let alpha = self.runner.update(&mut self.scenes, &mut self.backend);
+
+        self.backend.end_frame();
+        self.runner.end_frame();
+
+        alpha
+    }
+
+    /// Runs `frames` frames, stopping early and shutting the core down if the backend was asked to close
+    /// or the last scene is gone.
+    pub fn run(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            if self.backend.window_should_close() || self.is_finished() {
+                self.shutdown();
+                break;
+            }
+            self.step();
+        }
+
+        self
+    }
+
+    /// Plays back input recorded with `AppConfig::record_input`, one step per recorded frame,
+    /// and returns how many frames were played.
+    pub fn replay(&mut self, path: &str) -> Result<usize, Box<dyn Error>> {
+        self.runner.services.replay = Replay::play(path)?;
+
+        let mut frames = 0;
+        while self.runner.services.replay.has_next()
+            && !self.backend.window_should_close()
+            && !self.is_finished()
+        {
+            self.step();
+            frames += 1;
+        }
+
+        Ok(frames)
+    }
+
+    /// Calls `Core::shutdown`, unless it was already called.
+    pub fn shutdown(&mut self) {
+        if !self.shut_down {
+            self.shut_down = true;
+            self.runner.shutdown(&mut self.scenes);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.scenes.is_empty()
+    }
+}
+
+/// Puts the core on the scene stack while `HeadlessApp` keeps a handle to it.
+struct SharedCore<C: Core>(Rc<RefCell<C>>);
+
+impl<C: Core> Core for SharedCore<C> {
+    fn initialize(&mut self, context: &mut Context) {
+        self.0.borrow_mut().initialize(context);
+    }
+    fn update(&mut self, context: &mut Context) {
+        self.0.borrow_mut().update(context);
+    }
+    fn fixed_update(&mut self, context: &mut Context) {
+        self.0.borrow_mut().fixed_update(context);
+    }
+    fn draw(&self, frame: &mut Frame) {
+        self.0.borrow().draw(frame);
+    }
+    fn enter(&mut self, context: &mut Context) {
+        self.0.borrow_mut().enter(context);
+    }
+    fn exit(&mut self, context: &mut Context) {
+        self.0.borrow_mut().exit(context);
+    }
+    fn transition(&mut self) -> Option<Transition> {
+        self.0.borrow_mut().transition()
+    }
+    fn is_modal(&self) -> bool {
+        self.0.borrow().is_modal()
+    }
+    fn is_overlay(&self) -> bool {
+        self.0.borrow().is_overlay()
+    }
+    fn on_resize(&mut self, width: i32, height: i32) {
+        self.0.borrow_mut().on_resize(width, height);
+    }
+    fn on_focus_changed(&mut self, focused: bool) {
+        self.0.borrow_mut().on_focus_changed(focused);
+    }
+    fn on_file_dropped(&mut self, path: &Path) {
+        self.0.borrow_mut().on_file_dropped(path);
+    }
+    fn on_asset_reloaded(&mut self, context: &mut Context, path: &Path) {
+        self.0.borrow_mut().on_asset_reloaded(context, path);
+    }
+    fn shutdown(&mut self) {
+        self.0.borrow_mut().shutdown();
+    }
+}
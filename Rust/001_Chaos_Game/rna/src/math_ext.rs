@@ -1,13 +1,213 @@
-pub fn remap_range(
-    current: f64,
-    current_min: f64,
-    current_max: f64,
-    new_min: f64,
-    new_max: f64,
-) -> f64 {
-    new_min + (new_max - new_min) * (current - current_min) / (current_max - current_min)
+use raylib::prelude::*;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The floating point types the helpers in this module work with, i.e. `f32` and `f64`.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
+    /// Where `self` lies on the number line of representable values; neighbours differ by one.
+    fn ordinal(self) -> i64;
+}
+
+macro_rules! impl_float {
+    ($float:ty, $int:ty) => {
+        impl Float for $float {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+
+            fn abs(self) -> Self {
+                <$float>::abs(self)
+            }
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$float>::rem_euclid(self, rhs)
+            }
+            fn is_nan(self) -> bool {
+                <$float>::is_nan(self)
+            }
+            fn is_finite(self) -> bool {
+                <$float>::is_finite(self)
+            }
+            fn ordinal(self) -> i64 {
+                // Negative floats are stored as sign and magnitude, so their bits count the wrong way.
+                let bits = self.to_bits() as $int;
+                if bits < 0 {
+                    <$int>::MIN.wrapping_sub(bits) as i64
+                } else {
+                    bits as i64
+                }
+            }
+        }
+    };
 }
 
-pub fn almost_equal(value: f64, target: f64, precision: f64) -> bool {
+impl_float!(f32, i32);
+impl_float!(f64, i64);
+
+/// Blends from `a` to `b`; exactly `a` at zero and exactly `b` at one.
+pub fn lerp<T: Float>(a: T, b: T, t: T) -> T {
+    let half = T::ONE / (T::ONE + T::ONE);
+
+    if t < half {
+        a + (b - a) * t
+    } else {
+        b - (b - a) * (T::ONE - t)
+    }
+}
+
+/// The inverse of `lerp`: how far `value` lies between `a` and `b`, or zero if they are the same.
+pub fn inverse_lerp<T: Float>(a: T, b: T, value: T) -> T {
+    if a == b {
+        return T::ZERO;
+    }
+    (value - a) / (b - a)
+}
+
+/// Limits `value` to lie between `min` and `max`, which may be given in either order.
+pub fn clamp<T: Float>(value: T, min: T, max: T) -> T {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+/// Hermite interpolation between zero and one while `x` moves from `edge0` to `edge1`;
+/// a hard step if both edges are the same.
+pub fn smoothstep<T: Float>(edge0: T, edge1: T, x: T) -> T {
+    if edge0 == edge1 {
+        return if x < edge0 { T::ZERO } else { T::ONE };
+    }
+
+    let t = clamp(inverse_lerp(edge0, edge1, x), T::ZERO, T::ONE);
+    let two = T::ONE + T::ONE;
+    t * t * (two + T::ONE - two * t)
+}
+
+/// Wraps `value` around so that it lies within `[min, max)`, e.g. for angles; an empty range gives `min`.
+pub fn wrap<T: Float>(value: T, min: T, max: T) -> T {
+    let range = max - min;
+    if range.partial_cmp(&T::ZERO) != Some(std::cmp::Ordering::Greater) {
+        return min;
+    }
+
+    let result = min + (value - min).rem_euclid(range);
+    // Rounding can land exactly on `max` for values just below `min`.
+    if result < max {
+        result
+    } else {
+        min
+    }
+}
+
+/// Moves `current` towards `target` by at most `step` without overshooting it.
+pub fn approach<T: Float>(current: T, target: T, step: T) -> T {
+    let step = step.abs();
+
+    if current < target {
+        let next = current + step;
+        if next < target {
+            next
+        } else {
+            target
+        }
+    } else {
+        let next = current - step;
+        if next > target {
+            next
+        } else {
+            target
+        }
+    }
+}
+
+/// Maps `value` from one range onto another; an empty source range maps everything to `new_min`.
+pub fn remap_range<T: Float>(
+    value: T,
+    current_min: T,
+    current_max: T,
+    new_min: T,
+    new_max: T,
+) -> T {
+    lerp(
+        new_min,
+        new_max,
+        inverse_lerp(current_min, current_max, value),
+    )
+}
+
+/// Like `remap_range`, but never leaves the new range.
+pub fn remap_range_clamped<T: Float>(
+    value: T,
+    current_min: T,
+    current_max: T,
+    new_min: T,
+    new_max: T,
+) -> T {
+    let t = clamp(
+        inverse_lerp(current_min, current_max, value),
+        T::ZERO,
+        T::ONE,
+    );
+    lerp(new_min, new_max, t)
+}
+
+/// Whether `value` lies within `precision` of `target`.
+pub fn almost_equal<T: Float>(value: T, target: T, precision: T) -> bool {
     value - precision <= target && target <= value + precision
 }
+
+/// How many representable values lie between `a` and `b`; `None` if either is NaN.
+pub fn ulps_between<T: Float>(a: T, b: T) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    Some((a.ordinal() as i128 - b.ordinal() as i128).unsigned_abs() as u64)
+}
+
+/// Whether `a` and `b` are at most `max_ulps` representable values apart.
+pub fn almost_equal_ulps<T: Float>(a: T, b: T, max_ulps: u64) -> bool {
+    ulps_between(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+
+/// Whether `a` and `b` differ by at most `max_relative` times the larger of the two.
+pub fn almost_equal_relative<T: Float>(a: T, b: T, max_relative: T) -> bool {
+    if a == b {
+        return true;
+    }
+    if !a.is_finite() || !b.is_finite() {
+        return false;
+    }
+
+    let (a_abs, b_abs) = (a.abs(), b.abs());
+    let largest = if a_abs > b_abs { a_abs } else { b_abs };
+    (a - b).abs() <= largest * max_relative
+}
+
+/// Converts a position on the screen into the world space of `camera`, without needing a window.
+pub fn screen_to_world_2d(position: Vector2, camera: Camera2D) -> Vector2 {
+    let (sin, cos) = (-camera.rotation.to_radians()).sin_cos();
+    let x = (position.x - camera.offset.x) / camera.zoom;
+    let y = (position.y - camera.offset.y) / camera.zoom;
+
+    Vector2::new(
+        x * cos - y * sin + camera.target.x,
+        x * sin + y * cos + camera.target.y,
+    )
+}
@@ -0,0 +1,212 @@
+use std::f64::consts::TAU;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The name the seed of the app's generator is recorded under, alongside seeds registered through `Context::seed`.
+pub(crate) const SEED_NAME: &str = "random";
+
+/// Spreads the bits of a seed out, so that similar seeds still lead to unrelated sequences.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A seedable random number generator (xoshiro256**).
+///
+/// The same seed always produces the same sequence, on every platform and with every version of rna,
+/// which is what lets a recorded session be replayed.
+#[derive(Clone, Debug)]
+pub struct Random {
+    seed: u64,
+    state: [u64; 4],
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        let mut mix = seed;
+
+        Random {
+            seed,
+            state: [
+                split_mix(&mut mix),
+                split_mix(&mut mix),
+                split_mix(&mut mix),
+                split_mix(&mut mix),
+            ],
+        }
+    }
+
+    /// Seeds a generator from the clock, for when runs do not need to be repeatable.
+    pub fn from_entropy() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        let mut mix = nanos ^ ((std::process::id() as u64) << 32);
+
+        Random::new(split_mix(&mut mix))
+    }
+
+    /// The seed the generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// A number from zero up to, but not including, one.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A number from zero up to, but not including, one.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    /// A number below `bound`, without the bias a plain modulo would have (Lemire's method).
+    fn below(&mut self, bound: u64) -> u64 {
+        let mut product = self.next_u64() as u128 * bound as u128;
+
+        if (product as u64) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (product as u64) < threshold {
+                product = self.next_u64() as u128 * bound as u128;
+            }
+        }
+
+        (product >> 64) as u64
+    }
+
+    /// A number from `low` up to, but not including, `high`; every integer is equally likely.
+    /// An empty range gives `low`.
+    pub fn range<T: RandomRange>(&mut self, low: T, high: T) -> T {
+        T::sample(self, low, high)
+    }
+
+    /// Whether something that happens with the given `probability` happened.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Picks an item with a likelihood proportional to its `weight`; items without a positive weight are never picked.
+    pub fn weighted_choose<'a, T>(
+        &mut self,
+        items: &'a [T],
+        weight: impl Fn(&T) -> f64,
+    ) -> Option<&'a T> {
+        let weight = |item: &T| {
+            let weight = weight(item);
+            if weight > 0.0 && weight.is_finite() {
+                weight
+            } else {
+                0.0
+            }
+        };
+
+        let total: f64 = items.iter().map(weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let roll = self.next_f64() * total;
+        let mut accumulation = 0.0;
+        let mut picked = None;
+
+        for item in items.iter() {
+            let weight = weight(item);
+            if weight <= 0.0 {
+                continue;
+            }
+
+            accumulation += weight;
+            picked = Some(item);
+            if roll < accumulation {
+                break;
+            }
+        }
+
+        // Rounding can leave the roll just past the last accumulation, in which case the last item is picked.
+        picked
+    }
+
+    /// Puts `items` into a random order (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// A sample from the normal distribution (Box-Muller).
+    pub fn normal(&mut self, mean: f64, standard_deviation: f64) -> f64 {
+        // Never zero, as its logarithm is taken.
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+
+        mean + standard_deviation * (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+    }
+}
+
+/// A number type `Random::range` can pick from.
+pub trait RandomRange: Copy {
+    fn sample(random: &mut Random, low: Self, high: Self) -> Self;
+}
+
+macro_rules! impl_integer_range {
+    ($($integer:ty),*) => {
+        $(
+            impl RandomRange for $integer {
+                fn sample(random: &mut Random, low: Self, high: Self) -> Self {
+                    if low >= high {
+                        return low;
+                    }
+
+                    let span = (high as i128 - low as i128) as u64;
+                    (low as i128 + random.below(span) as i128) as $integer
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_range!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl RandomRange for f32 {
+    fn sample(random: &mut Random, low: Self, high: Self) -> Self {
+        // Rounding can land on `high` itself, which is outside of the range.
+        match low + (high - low) * random.next_f32() {
+            value if value < high => value,
+            _ => low,
+        }
+    }
+}
+
+impl RandomRange for f64 {
+    fn sample(random: &mut Random, low: Self, high: Self) -> Self {
+        match low + (high - low) * random.next_f64() {
+            value if value < high => value,
+            _ => low,
+        }
+    }
+}
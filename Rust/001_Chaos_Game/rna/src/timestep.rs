@@ -0,0 +1,46 @@
+/// Turns variable frame times into a steady series of fixed updates.
+pub(crate) struct FixedTimestep {
+    timestep: Option<f32>,
+    max_updates: u32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub(crate) fn new(timestep: Option<f32>, max_updates: u32) -> Self {
+        FixedTimestep {
+            timestep,
+            max_updates,
+            accumulator: 0.0,
+        }
+    }
+
+    /// The time each fixed update covers, or zero when fixed updates are disabled.
+    pub(crate) fn timestep(&self) -> f32 {
+        self.timestep.unwrap_or(0.0)
+    }
+
+    /// Calls `update` as many times as `frame_time` calls for, and returns the interpolation alpha.
+    pub(crate) fn advance<F: FnMut()>(&mut self, frame_time: f32, mut update: F) -> f32 {
+        let timestep = match self.timestep {
+            Some(timestep) if timestep > 0.0 => timestep,
+            _ => return 1.0,
+        };
+
+        self.accumulator += frame_time;
+
+        let mut updates = 0;
+        while self.accumulator >= timestep {
+            if updates >= self.max_updates {
+                // Falling this far behind means the simulation cannot keep up; skip ahead instead of spiraling.
+                self.accumulator %= timestep;
+                break;
+            }
+
+            update();
+            self.accumulator -= timestep;
+            updates += 1;
+        }
+
+        self.accumulator / timestep
+    }
+}
@@ -0,0 +1,97 @@
+use raylib::prelude::*;
+
+/// How a virtual resolution is scaled up to fill the window.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScaleMode {
+    /// Only whole multiples, which keeps pixel art crisp; falls back to `Fit` while the window is too small.
+    Integer,
+    /// As large as fits inside the window.
+    Fit,
+}
+
+/// Maps a fixed logical resolution onto a window of any size, centered and letterboxed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Viewport {
+    width: i32,
+    height: i32,
+    mode: ScaleMode,
+}
+
+impl Viewport {
+    pub fn new(width: i32, height: i32, mode: ScaleMode) -> Self {
+        Viewport {
+            width,
+            height,
+            mode,
+        }
+    }
+
+    /// The logical resolution.
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// How many window pixels a single logical pixel covers.
+    pub fn scale(&self, window_width: i32, window_height: i32) -> f32 {
+        let scale = (window_width as f32 / self.width as f32)
+            .min(window_height as f32 / self.height as f32);
+
+        match self.mode {
+            ScaleMode::Integer if scale >= 1.0 => scale.floor(),
+            _ => scale,
+        }
+    }
+
+    /// Where the logical image ends up inside the window; everything around it is letterboxed.
+    pub fn destination(&self, window_width: i32, window_height: i32) -> Rectangle {
+        let scale = self.scale(window_width, window_height);
+        let width = self.width as f32 * scale;
+        let height = self.height as f32 * scale;
+
+        Rectangle::new(
+            ((window_width as f32 - width) * 0.5).floor(),
+            ((window_height as f32 - height) * 0.5).floor(),
+            width,
+            height,
+        )
+    }
+
+    /// Translates a point in the window, e.g. the mouse, into logical space; points on the letterbox fall outside of it.
+    ///
+    /// A minimized window has nothing to translate from, so every point ends up at the logical origin.
+    pub fn to_logical(&self, window_width: i32, window_height: i32, point: Vector2) -> Vector2 {
+        let scale = self.scale(window_width, window_height);
+        if scale <= 0.0 {
+            return Vector2::new(0.0, 0.0);
+        }
+
+        let destination = self.destination(window_width, window_height);
+
+        Vector2::new(
+            (point.x - destination.x) / scale,
+            (point.y - destination.y) / scale,
+        )
+    }
+}
+
+/// Draws everything that was rendered into `target` onto the window, scaled and letterboxed.
+pub(crate) fn present(
+    d: &mut RaylibDrawHandle,
+    target: &RenderTexture2D,
+    viewport: &Viewport,
+    letterbox: Color,
+) {
+    let (width, height) = viewport.size();
+    let destination = viewport.destination(d.get_screen_width(), d.get_screen_height());
+
+    d.clear_background(letterbox);
+    // Render textures are stored upside down.
+    d.draw_texture_pro(
+        target.texture(),
+        Rectangle::new(0.0, 0.0, width as f32, -height as f32),
+        destination,
+        Vector2::new(0.0, 0.0),
+        0.0,
+        Color::WHITE,
+    );
+}
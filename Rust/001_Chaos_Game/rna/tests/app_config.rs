@@ -0,0 +1,58 @@
+use raylib::prelude::*;
+use rna::*;
+
+fn config_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("rna-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn settings_are_read_from_a_file() {
+    let path = config_file(
+        "window.txt",
+        "# Window\n\
+         title Grid World Deluxe\n\
+         size 1280,720\n\
+         target_fps 144\n\
+         fullscreen_key f8\n\
+         msaa_4x true\n\
+         position 10,20\n\
+         min_size 320,180\n\
+         max_size 1920,1080\n\
+         run_unfocused true\n\
+         record_input session.txt\n\
+         seed 42\n",
+    );
+
+    let mut config = AppConfig::new();
+    config.load(&path).unwrap();
+
+    assert_eq!(config.title, "Grid World Deluxe");
+    assert_eq!(config.window_size, (1280, 720));
+    assert_eq!(config.target_fps, Some(144));
+    assert_eq!(config.fullscreen_key, Some(KeyboardKey::KEY_F8));
+    assert!(config.msaa_4x);
+    assert!(!config.high_dpi);
+    assert_eq!(config.position, Some((10, 20)));
+    assert_eq!(config.min_size, Some((320, 180)));
+    assert_eq!(config.max_size, Some((1920, 1080)));
+    assert!(config.run_unfocused);
+    assert_eq!(config.record_input.as_deref(), Some("session.txt"));
+    assert_eq!(config.replay_input, None);
+    assert_eq!(config.seed, Some(42));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn unknown_settings_are_errors() {
+    let path = config_file("unknown.txt", "size 640,360\nwindowed yes\n");
+
+    match AppConfig::builder().load(&path) {
+        Err(RnaError::InvalidConfig(_, reason)) => assert!(reason.contains("line 2")),
+        _ => panic!("expected an invalid config"),
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,101 @@
+use raylib::prelude::*;
+use rna::*;
+
+#[derive(Default)]
+struct Counter {
+    initialized: bool,
+    screen_width: i32,
+    updates: usize,
+    fixed_updates: usize,
+    jumps: usize,
+    held: usize,
+    frame: u64,
+    fixed_delta_time: f32,
+}
+
+impl Core for Counter {
+    fn initialize(&mut self, context: &mut Context) {
+        self.initialized = true;
+        self.screen_width = context.get_screen_width();
+    }
+
+    fn update(&mut self, context: &mut Context) {
+        self.updates += 1;
+        self.frame = context.frame();
+        context.watch("updates", self.updates);
+
+        if context.is_key_pressed(KeyboardKey::KEY_SPACE) {
+            self.jumps += 1;
+        }
+
+        if context.is_key_down(KeyboardKey::KEY_SPACE) {
+            self.held += 1;
+        }
+    }
+
+    fn fixed_update(&mut self, context: &mut Context) {
+        self.fixed_updates += 1;
+        self.fixed_delta_time = context.delta_time();
+    }
+
+    fn draw(&self, _: &mut Frame) {}
+}
+
+#[test]
+fn initializes_before_the_first_update() {
+    let mut app = HeadlessApp::new(Counter::default(), 320, 240);
+    app.step();
+
+    assert!(app.core().initialized);
+    assert_eq!(app.core().screen_width, 320);
+    assert_eq!(app.core().updates, 1);
+}
+
+#[test]
+fn key_presses_last_a_single_frame() {
+    let mut app = HeadlessApp::new(Counter::default(), 320, 240);
+
+    app.backend_mut().press_key(KeyboardKey::KEY_SPACE);
+    app.run(3);
+    app.backend_mut().release_key(KeyboardKey::KEY_SPACE);
+    app.run(2);
+
+    assert_eq!(app.core().updates, 5);
+    assert_eq!(app.core().frame, 4);
+    assert_eq!(app.core().jumps, 1);
+    assert_eq!(app.core().held, 3);
+}
+
+#[test]
+fn fixed_updates_follow_simulated_time() {
+    let mut app = HeadlessApp::new(Counter::default(), 320, 240).with_fixed_timestep(0.1, 5);
+    app.backend_mut().set_frame_time(0.25);
+
+    let alpha = app.step();
+    assert_eq!(app.core().fixed_updates, 2);
+    assert_eq!(app.core().fixed_delta_time, 0.1);
+    assert!((alpha - 0.5).abs() < 1e-4);
+
+    app.step();
+    assert_eq!(app.core().fixed_updates, 5);
+    assert!((app.backend_mut().get_time() - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn watch_values_are_reported() {
+    let mut app = HeadlessApp::new(Counter::default(), 320, 240);
+    assert_eq!(app.watch("updates"), None);
+
+    app.run(3);
+    assert_eq!(app.watch("updates"), Some("3"));
+}
+
+#[test]
+fn stops_once_closed() {
+    let mut app = HeadlessApp::new(Counter::default(), 320, 240);
+    app.run(2);
+    app.backend_mut().close();
+    app.run(10);
+
+    assert_eq!(app.core().updates, 2);
+}
@@ -0,0 +1,75 @@
+use raylib::prelude::*;
+use rna::*;
+
+#[test]
+fn actions_follow_their_bindings() {
+    let mut backend = HeadlessBackend::new(320, 240);
+    let mut input = InputMap::new();
+    input.bind("pause", Binding::Key(KeyboardKey::KEY_P));
+
+    backend.press_key(KeyboardKey::KEY_P);
+    assert!(input.is_pressed(&backend, "pause"));
+    assert!(!input.is_pressed(&backend, "unbound"));
+
+    input.rebind("pause", Binding::Key(KeyboardKey::KEY_SPACE));
+    assert!(!input.is_pressed(&backend, "pause"));
+
+    backend.release_key(KeyboardKey::KEY_P);
+    backend.end_frame();
+    backend.press_key(KeyboardKey::KEY_SPACE);
+    assert!(input.is_pressed(&backend, "pause"));
+    assert_eq!(
+        InputMap::listen(&backend),
+        Some(Binding::Key(KeyboardKey::KEY_SPACE))
+    );
+}
+
+#[test]
+fn wheel_bindings_form_an_axis() {
+    let mut backend = HeadlessBackend::new(320, 240);
+    let mut input = InputMap::new();
+    input.bind("zoom_in", Binding::WheelUp);
+    input.bind("zoom_out", Binding::WheelDown);
+
+    backend.scroll(-2.0);
+    assert_eq!(input.axis(&backend, "zoom_in", "zoom_out"), -2.0);
+
+    backend.end_frame();
+    assert_eq!(input.axis(&backend, "zoom_in", "zoom_out"), 0.0);
+}
+
+#[test]
+fn bindings_survive_a_round_trip_through_a_file() {
+    let path = std::env::temp_dir().join("rna-input-test.txt");
+    let path = path.to_str().unwrap();
+
+    let mut input = InputMap::new();
+    input.bind("zoom_in", Binding::WheelUp);
+    input.bind("zoom_in", Binding::Key(KeyboardKey::KEY_EQUAL));
+    input.bind("pan", Binding::MouseButton(MouseButton::MOUSE_LEFT_BUTTON));
+    input.save(path).unwrap();
+
+    let mut loaded = InputMap::new();
+    loaded.load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(loaded.actions(), vec!["pan", "zoom_in"]);
+    assert_eq!(loaded.bindings("zoom_in"), input.bindings("zoom_in"));
+    assert_eq!(loaded.bindings("pan"), input.bindings("pan"));
+}
+
+#[test]
+fn unknown_bindings_are_rejected() {
+    let path = std::env::temp_dir().join("rna-input-invalid-test.txt");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "# Comment\nstep N\npause P,NOT_A_KEY\n").unwrap();
+
+    let mut input = InputMap::new();
+    input.bind("step", Binding::Key(KeyboardKey::KEY_S));
+    let result = input.load(path);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(result.is_err());
+    // The valid line before the invalid one is not applied on its own.
+    assert_eq!(input.bindings("step"), [Binding::Key(KeyboardKey::KEY_S)]);
+}
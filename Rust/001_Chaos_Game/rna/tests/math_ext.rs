@@ -0,0 +1,187 @@
+use proptest::prelude::*;
+use rna::*;
+
+// Every property is checked for both float types; values stay small enough that ranges cannot overflow.
+macro_rules! properties {
+    ($name:ident, $float:ident) => {
+        mod $name {
+            use super::*;
+
+            const LIMIT: $float = 1e6;
+
+            fn value() -> impl Strategy<Value = $float> {
+                -LIMIT..LIMIT
+            }
+
+            fn unit() -> impl Strategy<Value = $float> {
+                0.0 as $float..=1.0
+            }
+
+            proptest! {
+                #[test]
+                fn lerp_hits_both_ends_exactly(a in value(), b in value()) {
+                    prop_assert_eq!(lerp(a, b, 0.0), a);
+                    prop_assert_eq!(lerp(a, b, 1.0), b);
+                    prop_assert_eq!(lerp(a, a, 0.3), a);
+                }
+
+                #[test]
+                fn lerp_stays_between_its_ends(a in value(), b in value(), t in unit()) {
+                    let result = lerp(a, b, t);
+                    prop_assert!(a.min(b) <= result && result <= a.max(b));
+                }
+
+                #[test]
+                fn lerp_is_monotonic(a in value(), b in value(), t in unit(), u in unit()) {
+                    let (t, u) = (t.min(u), t.max(u));
+                    if a <= b {
+                        prop_assert!(lerp(a, b, t) <= lerp(a, b, u));
+                    } else {
+                        prop_assert!(lerp(a, b, t) >= lerp(a, b, u));
+                    }
+                }
+
+                #[test]
+                fn inverse_lerp_undoes_lerp(a in value(), b in value(), t in unit()) {
+                    prop_assume!((b - a).abs() > 1000.0);
+                    let result = inverse_lerp(a, b, lerp(a, b, t));
+                    prop_assert!(almost_equal(result, t, 1e-3));
+                }
+
+                #[test]
+                fn inverse_lerp_of_an_empty_range_is_zero(a in value(), x in value()) {
+                    prop_assert_eq!(inverse_lerp(a, a, x), 0.0);
+                }
+
+                #[test]
+                fn clamp_stays_within_bounds(x in value(), a in value(), b in value()) {
+                    let result = clamp(x, a, b);
+                    prop_assert!(a.min(b) <= result && result <= a.max(b));
+                    if a.min(b) <= x && x <= a.max(b) {
+                        prop_assert_eq!(result, x);
+                    }
+                }
+
+                #[test]
+                fn smoothstep_is_a_monotonic_unit_curve(
+                    a in value(),
+                    b in value(),
+                    x in value(),
+                    y in value(),
+                ) {
+                    let (low, high) = (smoothstep(a, b, x.min(y)), smoothstep(a, b, x.max(y)));
+                    prop_assert!((0.0..=1.0).contains(&low) && (0.0..=1.0).contains(&high));
+                    if a <= b {
+                        prop_assert!(low <= high);
+                    } else {
+                        prop_assert!(low >= high);
+                    }
+                }
+
+                #[test]
+                fn smoothstep_with_equal_edges_is_a_step(edge in value(), x in value()) {
+                    let expected = if x < edge { 0.0 } else { 1.0 };
+                    prop_assert_eq!(smoothstep(edge, edge, x), expected);
+                }
+
+                #[test]
+                fn wrap_lands_in_the_half_open_range(x in value(), min in value(), size in 0.001 as $float..1000.0) {
+                    let max = min + size;
+                    prop_assume!(min < max);
+                    let result = wrap(x, min, max);
+                    prop_assert!(min <= result && result < max);
+                    if min <= x && x < max {
+                        prop_assert_eq!(result, x);
+                    }
+                }
+
+                #[test]
+                fn wrap_of_an_empty_range_is_its_start(x in value(), min in value()) {
+                    prop_assert_eq!(wrap(x, min, min), min);
+                }
+
+                #[test]
+                fn approach_never_overshoots(current in value(), target in value(), step in value()) {
+                    let result = approach(current, target, step);
+                    prop_assert!(current.min(target) <= result && result <= current.max(target));
+                    if (target - current).abs() <= step.abs() {
+                        prop_assert_eq!(result, target);
+                    }
+                }
+
+                #[test]
+                fn remap_range_maps_the_ends_onto_each_other(
+                    a in value(),
+                    b in value(),
+                    c in value(),
+                    d in value(),
+                ) {
+                    prop_assume!(a != b);
+                    prop_assert_eq!(remap_range(a, a, b, c, d), c);
+                    prop_assert_eq!(remap_range(b, a, b, c, d), d);
+                }
+
+                #[test]
+                fn remap_range_of_an_empty_range_is_the_new_start(
+                    a in value(),
+                    x in value(),
+                    c in value(),
+                    d in value(),
+                ) {
+                    let result = remap_range(x, a, a, c, d);
+                    prop_assert!(result.is_finite());
+                    prop_assert_eq!(result, c);
+                    prop_assert_eq!(remap_range_clamped(x, a, a, c, d), c);
+                }
+
+                #[test]
+                fn remap_range_clamped_stays_in_the_new_range(
+                    x in value(),
+                    a in value(),
+                    b in value(),
+                    c in value(),
+                    d in value(),
+                ) {
+                    let result = remap_range_clamped(x, a, b, c, d);
+                    prop_assert!(c.min(d) <= result && result <= c.max(d));
+                }
+
+                #[test]
+                fn neighbours_are_one_ulp_apart(x in value()) {
+                    let next = $float::from_bits(x.to_bits() + 1);
+                    prop_assert_eq!(ulps_between(x, next), Some(1));
+                    prop_assert!(almost_equal_ulps(x, next, 1));
+                    prop_assert!(!almost_equal_ulps(x, next, 0));
+                    prop_assert_eq!(ulps_between(x, x), Some(0));
+                }
+
+                #[test]
+                fn ulps_are_symmetric(a in value(), b in value()) {
+                    prop_assert_eq!(ulps_between(a, b), ulps_between(b, a));
+                }
+
+                #[test]
+                fn relative_comparison_scales_with_magnitude(x in value(), scale in 1.0 as $float..1000.0) {
+                    prop_assume!(x != 0.0);
+                    let nudged = x * (1.0 + 1e-4);
+                    prop_assert!(almost_equal_relative(x, nudged, 1e-3));
+                    prop_assert!(almost_equal_relative(x * scale, nudged * scale, 1e-3));
+                    prop_assert!(!almost_equal_relative(x, x * 1.5, 1e-3));
+                }
+            }
+
+            #[test]
+            fn special_values_compare_sensibly() {
+                assert_eq!(ulps_between(0.0, -0.0 as $float), Some(0));
+                assert_eq!(ulps_between($float::NAN, 1.0), None);
+                assert!(!almost_equal_ulps($float::NAN, $float::NAN, u64::MAX));
+                assert!(almost_equal_relative($float::INFINITY, $float::INFINITY, 0.0));
+                assert!(!almost_equal_relative($float::INFINITY, $float::MAX, 1.0));
+                assert!(!almost_equal_relative($float::NAN, $float::NAN, 1.0));
+            }
+        }
+    };
+}
+
+properties!(single, f32);
+properties!(double, f64);
@@ -0,0 +1,87 @@
+use raylib::prelude::*;
+use rna::*;
+
+struct Session {
+    seed: u64,
+    preferred_seed: u64,
+    jumps: usize,
+    clicks: Vec<(f32, f32)>,
+    scrolled: f32,
+    elapsed: f32,
+}
+
+impl Session {
+    fn new(preferred_seed: u64) -> Self {
+        Session {
+            seed: 0,
+            preferred_seed,
+            jumps: 0,
+            clicks: Vec::new(),
+            scrolled: 0.0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl Core for Session {
+    fn initialize(&mut self, context: &mut Context) {
+        self.seed = context.seed("session", self.preferred_seed);
+    }
+    fn update(&mut self, context: &mut Context) {
+        self.elapsed += context.delta_time();
+        self.scrolled += context.get_mouse_wheel_move();
+
+        if context.is_key_pressed(KeyboardKey::KEY_SPACE) {
+            self.jumps += 1;
+        }
+
+        if context.is_mouse_button_pressed(MouseButton::MOUSE_LEFT_BUTTON) {
+            let position = context.get_mouse_position();
+            self.clicks.push((position.x, position.y));
+        }
+    }
+    fn draw(&self, _: &mut Frame) {}
+}
+
+#[test]
+fn replays_match_the_recorded_session() {
+    let path = std::env::temp_dir().join(format!("rna-{}-session.txt", std::process::id()));
+    let path = path.to_string_lossy();
+
+    let mut app = HeadlessApp::new(Session::new(7), 320, 240).with_input_recording(&path);
+    app.backend_mut().set_frame_time(0.1);
+    app.backend_mut().press_key(KeyboardKey::KEY_SPACE);
+    app.step();
+    app.backend_mut().release_key(KeyboardKey::KEY_SPACE);
+    app.backend_mut().set_frame_time(1. / 3.);
+    app.backend_mut()
+        .set_mouse_position(Vector2::new(12.25, 40.0));
+    app.backend_mut()
+        .press_mouse_button(MouseButton::MOUSE_LEFT_BUTTON);
+    app.backend_mut().scroll(-1.5);
+    app.run(3);
+    app.shutdown();
+
+    let mut replayed = HeadlessApp::new(Session::new(99), 640, 480);
+    assert_eq!(replayed.replay(&path).unwrap(), 4);
+
+    let (recorded, replayed) = (app.core(), replayed.core());
+    assert_eq!(replayed.seed, 7);
+    assert_eq!(replayed.jumps, recorded.jumps);
+    assert_eq!(replayed.clicks, vec![(12.25, 40.0)]);
+    assert_eq!(replayed.scrolled, recorded.scrolled);
+    assert_eq!(replayed.elapsed, recorded.elapsed);
+
+    std::fs::remove_file(path.as_ref()).unwrap();
+}
+
+#[test]
+fn malformed_recordings_are_errors() {
+    let path = std::env::temp_dir().join(format!("rna-{}-malformed.txt", std::process::id()));
+    std::fs::write(&path, "down SPACE\n").unwrap();
+
+    let mut app = HeadlessApp::new(Session::new(1), 320, 240);
+    assert!(app.replay(&path.to_string_lossy()).is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
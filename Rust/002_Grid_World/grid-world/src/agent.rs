@@ -0,0 +1,129 @@
+use crate::world::{Action, State, World};
+use rna::Random;
+
+pub struct Agent {
+    pub position: (usize, usize),
+    pub previous: (usize, usize),
+    steps: usize,
+    episode_return: f32,
+    episodes: usize,
+    average_return: f32,
+}
+
+impl Agent {
+    pub fn new(start: (usize, usize)) -> Self {
+        Agent {
+            position: start,
+            previous: start,
+            steps: 0,
+            episode_return: 0.0,
+            episodes: 0,
+            average_return: 0.0,
+        }
+    }
+
+    pub fn episodes(&self) -> usize {
+        self.episodes
+    }
+
+    pub fn average_return(&self) -> f32 {
+        self.average_return
+    }
+
+    pub fn respawn(&mut self, start: (usize, usize)) {
+        self.position = start;
+        self.previous = start;
+        self.steps = 0;
+        self.episode_return = 0.0;
+    }
+
+    pub fn reset(&mut self, start: (usize, usize)) {
+        self.respawn(start);
+        self.episodes = 0;
+        self.average_return = 0.0;
+    }
+
+    /// Takes the action the policy prescribes, letting the world's noise decide where the agent actually ends up.
+    pub fn step(
+        &mut self,
+        world: &World,
+        policy: &[Action],
+        discount: f32,
+        noise: f32,
+        random: &mut Random,
+    ) {
+        let state = State::new(self.position.0, self.position.1);
+
+        if !world.valid_position(&state) {
+            return;
+        }
+
+        let action = policy[state.y * world.width + state.x];
+        let transitions = match world.transition(&state, action, noise) {
+            Some(transitions) => transitions,
+            None => return,
+        };
+
+        let outcome =
+            match random.weighted_choose(&transitions, |(probability, _)| *probability as f64) {
+                Some((_, action)) => *action,
+                None => return,
+            };
+        let reward = world.reward(&state, outcome) * discount.powi(self.steps as i32);
+        self.episode_return += reward;
+        self.steps += 1;
+
+        match outcome {
+            Action::Move(direction) => {
+                let target = world.move_to(&state, direction);
+                self.previous = self.position;
+                self.position = (target.x, target.y);
+
+                // A policy that never reaches an exit would otherwise keep an episode going forever.
+                if self.steps >= 1000 {
+                    self.respawn(world.start);
+                }
+            }
+            Action::Exit => {
+                self.episodes += 1;
+                self.average_return +=
+                    (self.episode_return - self.average_return) / self.episodes as f32;
+                self.respawn(world.start);
+            }
+            Action::None => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::Direction;
+
+    #[test]
+    fn agents_follow_the_policy_and_respawn_after_exiting() {
+        let mut world = World::new(2, 2);
+        world.add_exit(1, 0, 1.0);
+        world.start = (0, 1);
+
+        let up = Action::Move(Direction::Up);
+        let right = Action::Move(Direction::Right);
+        let policy = [right, Action::Exit, up, up];
+
+        let mut agent = Agent::new(world.start);
+        let mut random = Random::new(0);
+
+        agent.step(&world, &policy, 0.5, 0.0, &mut random);
+        assert_eq!(agent.position, (0, 0));
+        assert_eq!(agent.previous, (0, 1));
+
+        agent.step(&world, &policy, 0.5, 0.0, &mut random);
+        assert_eq!(agent.position, (1, 0));
+
+        agent.step(&world, &policy, 0.5, 0.0, &mut random);
+        assert_eq!(agent.position, world.start);
+        assert_eq!(agent.episodes(), 1);
+        // The exit is reached on the third step, so its reward is discounted twice.
+        assert_eq!(agent.average_return(), 0.25);
+    }
+}
@@ -1,6 +1,9 @@
+use crate::agent::Agent;
+use crate::history::{History, Snapshot};
 use crate::world::{Action, Analysis, Direction, State, World};
 use raylib::prelude::*;
 use rna::*;
+use std::path::Path;
 
 enum Mode {
     Value,
@@ -8,17 +11,50 @@ enum Mode {
 }
 
 pub struct Game {
+    input: InputMap,
     camera: Camera2D,
+    drag: Option<Vector2>,
     world: World,
+    path: String,
+    world_file: Option<Handle<String>>,
+    watch_world: bool,
     analysis: Analysis,
     mode: Mode,
     discount: f32,
     noise: f32,
+    epsilon: f32,
     show_policy: bool,
-    target: f32,
-    accumulator: f32,
+    user_policy: Option<Vec<Action>>,
+    show_user_policy: bool,
+    show_numbers: bool,
+    color_map: ColorMap,
+    scaling: Scaling,
+    show_legend: bool,
+    editing: bool,
+    hovered: Option<(usize, usize)>,
+    agent: Agent,
+    show_agent: bool,
+    history: History,
+    compare: Option<usize>,
+    scrubbing: bool,
+    paused: bool,
+    iteration: usize,
+    residual: f32,
+    converged: bool,
+    speed: i32,
+    ticks: u32,
+    agent_ticks: u32,
+    previous: Option<Analysis>,
+    fade: Option<TweenHandle<f32>>,
+    seed: Option<u64>,
 }
 
+/// How many fixed updates run per second; the solver and the agent both advance in fixed updates.
+pub const TICK_RATE: u32 = 40;
+
+/// How many fixed updates pass between two steps of the agent.
+const AGENT_TICKS: u32 = 10;
+
 impl Game {
     pub fn new(mut args: std::env::Args) -> Self {
         args.next();
@@ -27,7 +63,14 @@ impl Game {
         let mut discount = 0.9;
         let mut noise = 0.2;
         let mut epsilon = 0.0001;
+        let mut living_reward = None;
         let mut path = String::new();
+        let mut policy_path = String::new();
+        let mut history_megabytes = 64;
+        let mut color_map = ColorMap::Classic;
+        let mut scaling = Scaling::Independent;
+        let mut input_path = String::new();
+        let mut seed = None;
 
         let args: Vec<String> = args.collect();
         for i in (0..args.len()).step_by(2) {
@@ -42,12 +85,45 @@ impl Game {
                     "-N" | "--noise" => {
                         noise = value.parse::<f32>().unwrap_or(noise);
                     }
+                    "-R" | "--living-reward" => {
+                        living_reward = value.parse::<f32>().ok().or(living_reward);
+                    }
                     "-E" | "--epsilon" => {
                         epsilon = value.parse::<f32>().unwrap_or(epsilon);
                     }
                     "-P" | "--path" => {
                         path = String::from(value);
                     }
+                    "-F" | "--policy-file" => {
+                        policy_path = String::from(value);
+                    }
+                    // How many megabytes earlier iterations may take up.
+                    "-H" | "--history" => match value.parse::<usize>() {
+                        Ok(megabytes) => history_megabytes = megabytes,
+                        Err(_) => {
+                            eprintln!(
+                                "Could not parse '{}' as a history limit; -H takes a number of megabytes.",
+                                value
+                            );
+                            std::process::exit(2);
+                        }
+                    },
+                    "-C" | "--color-map" => {
+                        color_map = ColorMap::from_name(value).unwrap_or(color_map);
+                    }
+                    "-I" | "--input" => {
+                        input_path = String::from(value);
+                    }
+                    "--seed" => {
+                        seed = value.parse::<u64>().ok().or(seed);
+                    }
+                    "-S" | "--scaling" => {
+                        scaling = match value.to_lowercase().as_str() {
+                            "symmetric" => Scaling::Symmetric,
+                            "independent" => Scaling::Independent,
+                            _ => scaling,
+                        };
+                    }
                     _ => (),
                 }
             }
@@ -58,245 +134,1166 @@ impl Game {
         world.add_exit(3, 0, 1.0);
         world.add_exit(3, 1, -1.0);
 
-        let world = World::load(path.as_str()).unwrap_or(world);
+        // A world file that does not exist yet is created by the first edit, but one that exists is never replaced
+        // by the built-in world just because it could not be read.
+        let mut world = match World::load(path.as_str()) {
+            Ok(world) => world,
+            Err(error) if Path::new(&path).exists() => {
+                eprintln!("Could not load world from '{}': {}", path, error);
+                std::process::exit(2);
+            }
+            Err(_) => world,
+        };
+
+        if let Some(living_reward) = living_reward {
+            world.living_reward = living_reward;
+        }
+
+        let user_policy = if policy_path.is_empty() {
+            None
+        } else {
+            match world.load_policy(policy_path.as_str()) {
+                Ok(policy) => {
+                    Game::print_policy_report(&world, discount, noise, epsilon, &policy);
+                    Some(policy)
+                }
+                Err(error) => {
+                    eprintln!("Could not load policy file '{}': {}", policy_path, error);
+                    None
+                }
+            }
+        };
 
         let mode = match mode.as_str() {
             "policy" => Mode::Policy,
             _ => Mode::Value,
         };
 
+        let analysis = Game::create_analysis(&world, &mode);
+        let agent = Agent::new(world.start);
+        let history = History::new(
+            history_megabytes.saturating_mul(1024 * 1024),
+            Snapshot {
+                iteration: 0,
+                residual: 0.0,
+                converged: false,
+                analysis: analysis.clone(),
+            },
+        );
+
+        Game {
+            input: Game::create_input(input_path.as_str()),
+            camera: Game::create_camera(),
+            drag: None,
+            world,
+            path,
+            world_file: None,
+            watch_world: true,
+            analysis,
+            mode,
+            discount,
+            noise,
+            epsilon,
+            show_policy: false,
+            show_user_policy: user_policy.is_some(),
+            user_policy,
+            show_numbers: false,
+            color_map,
+            scaling,
+            show_legend: true,
+            editing: false,
+            hovered: None,
+            agent,
+            show_agent: false,
+            history,
+            compare: None,
+            scrubbing: false,
+            paused: false,
+            iteration: 0,
+            residual: 0.0,
+            converged: false,
+            speed: -3,
+            ticks: 0,
+            agent_ticks: 0,
+            previous: None,
+            fade: None,
+            seed,
+        }
+    }
+
+    /// The seed given with `--seed`, if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    fn create_input(path: &str) -> InputMap {
+        let mut input = InputMap::new();
+        let defaults = [
+            ("toggle_policy", "SPACE"),
+            ("toggle_user_policy", "O"),
+            ("toggle_numbers", "V"),
+            ("next_color_map", "M"),
+            ("toggle_scaling", "K"),
+            ("toggle_legend", "L"),
+            ("toggle_editor", "E"),
+            ("toggle_agent", "G"),
+            ("pause", "P"),
+            ("step", "N"),
+            ("speed_up", "EQUAL"),
+            ("slow_down", "MINUS"),
+            ("reset", "R"),
+            ("converge", "C"),
+            ("decrease_discount", "Q"),
+            ("increase_discount", "W"),
+            ("decrease_noise", "A"),
+            ("increase_noise", "S"),
+            ("decrease_living_reward", "Z"),
+            ("increase_living_reward", "X"),
+            ("previous_iteration", "COMMA"),
+            ("next_iteration", "PERIOD"),
+            ("compare", "B"),
+            ("scrub", "MOUSE_LEFT"),
+            ("reset_camera", "ZERO"),
+            ("zoom_in", "WHEEL_UP"),
+            ("zoom_out", "WHEEL_DOWN"),
+            ("pan", "MOUSE_LEFT"),
+            ("grow_width", "RIGHT"),
+            ("shrink_width", "LEFT"),
+            ("grow_height", "DOWN"),
+            ("shrink_height", "UP"),
+            ("toggle_wall", "MOUSE_LEFT"),
+            ("toggle_exit", "MOUSE_RIGHT"),
+            ("place_start", "MOUSE_MIDDLE"),
+            ("increase_reward", "WHEEL_UP"),
+            ("decrease_reward", "WHEEL_DOWN"),
+        ];
+
+        for (action, name) in defaults.iter() {
+            if let Some(binding) = Binding::from_name(name) {
+                input.bind(action, binding);
+            }
+        }
+
+        if !path.is_empty() {
+            if let Err(error) = input.load(path) {
+                eprintln!("Could not load input file '{}': {}", path, error);
+            }
+        }
+
+        input
+    }
+
+    fn create_camera() -> Camera2D {
+        Camera2D {
+            zoom: 1.0,
+            target: Vector2::new(0.0, 0.0),
+            rotation: 0.0,
+            offset: Vector2::new(0.0, 0.0),
+        }
+    }
+
+    fn create_analysis(world: &World, mode: &Mode) -> Analysis {
         let policy = match mode {
             Mode::Policy => world.generate_random_policy(),
             Mode::Value => vec![Action::None; world.area()],
         };
 
-        let analysis = Analysis {
+        Analysis {
             policy,
             values: vec![0.0; world.area()],
             q_values: vec![[0.0; 4]; world.area()],
             min_value: 0.0,
             max_value: 0.0,
+        }
+    }
+
+    fn print_policy_report(
+        world: &World,
+        discount: f32,
+        noise: f32,
+        epsilon: f32,
+        policy: &[Action],
+    ) {
+        let values =
+            world.policy_evaluation(discount, noise, epsilon, policy, &vec![0.0; world.area()]);
+        let (optimal_values, _) = world.optimal_values(discount, noise, epsilon);
+
+        println!("{:<10}{:>12}{:>12}{:>12}", "State", "V^pi", "V*", "Regret");
+
+        let mut total_regret = 0.0;
+        let mut max_regret = 0.0;
+        let mut states = 0;
+
+        for y in 0..world.height {
+            for x in 0..world.width {
+                let index = y * world.width + x;
+                if !world.valid_position(&State::new(x, y)) {
+                    continue;
+                }
+
+                let regret = optimal_values[index] - values[index];
+                println!(
+                    "{:<10}{:>12.4}{:>12.4}{:>12.4}",
+                    format!("({},{})", x, y),
+                    values[index],
+                    optimal_values[index],
+                    regret
+                );
+
+                total_regret += regret;
+                max_regret = regret.max(max_regret);
+                states += 1;
+            }
+        }
+
+        if states > 0 {
+            println!(
+                "Mean regret: {:.4}, max regret: {:.4}",
+                total_regret / states as f32,
+                max_regret
+            );
+        }
+    }
+
+    /// Returns the size of a cell as well as the horizontal and vertical offset of the grid.
+    fn layout(&self, screen_width: i32, screen_height: i32) -> (usize, usize, usize) {
+        let size = screen_width as usize / self.world.width;
+        let size = size.min(screen_height as usize / self.world.height);
+        // Worlds with more cells than pixels still get a cell per pixel; the camera can zoom out to see all of them.
+        let size = size.max(1);
+        let x_offset = (screen_width as usize).saturating_sub(self.world.width * size) / 2;
+        let y_offset = (screen_height as usize).saturating_sub(self.world.height * size) / 2;
+
+        (size, x_offset, y_offset)
+    }
+
+    fn cell_at(&self, r: &dyn Backend, position: Vector2) -> Option<(usize, usize)> {
+        let (size, x_offset, y_offset) = self.layout(r.get_screen_width(), r.get_screen_height());
+        let position = screen_to_world_2d(position, self.camera);
+
+        let x = position.x - x_offset as f32;
+        let y = position.y - y_offset as f32;
+
+        if size == 0 || x < 0.0 || y < 0.0 {
+            return None;
+        }
+
+        let x = x as usize / size;
+        let y = y as usize / size;
+
+        if x >= self.world.width || y >= self.world.height {
+            return None;
+        }
+
+        Some((x, y))
+    }
+
+    fn update_camera(&mut self, r: &dyn Backend) {
+        let mouse = r.get_mouse_position();
+
+        if self.input.is_pressed(r, "reset_camera") {
+            self.camera = Game::create_camera();
+        }
+
+        // While editing, the wheel belongs to the reward of whichever exit is under the cursor.
+        let editing_exit = self.editing
+            && self
+                .hovered
+                .is_some_and(|(x, y)| self.world.can_exit(&State::new(x, y)));
+
+        let wheel = self.input.axis(r, "zoom_in", "zoom_out");
+        if wheel != 0.0 && !editing_exit {
+            // Zoom around the cursor by pinning the point beneath it in place.
+            self.camera.target = screen_to_world_2d(mouse, self.camera);
+            self.camera.offset = mouse;
+            self.camera.zoom = (self.camera.zoom * 1.1_f32.powf(wheel)).clamp(0.1, 64.0);
+        }
+
+        if !self.editing && self.input.is_down(r, "pan") {
+            if let Some(previous) = self.drag {
+                self.camera.target.x -= (mouse.x - previous.x) / self.camera.zoom;
+                self.camera.target.y -= (mouse.y - previous.y) / self.camera.zoom;
+            }
+            self.drag = Some(mouse);
+        } else {
+            self.drag = None;
+        }
+    }
+
+    fn update_editor(&mut self, r: &dyn Backend) {
+        let mut edited = false;
+
+        let (width, height) = (self.world.width, self.world.height);
+        if self.input.is_pressed(r, "grow_width") {
+            self.world.resize(width + 1, height);
+            edited = true;
+        }
+        if self.input.is_pressed(r, "shrink_width") && width > 1 {
+            self.world.resize(width - 1, height);
+            edited = true;
+        }
+        if self.input.is_pressed(r, "grow_height") {
+            self.world.resize(width, height + 1);
+            edited = true;
+        }
+        if self.input.is_pressed(r, "shrink_height") && height > 1 {
+            self.world.resize(width, height - 1);
+            edited = true;
+        }
+
+        if let Some((x, y)) = self.hovered {
+            let state = State::new(x, y);
+
+            if self.input.is_pressed(r, "toggle_wall") {
+                if self.world.valid_position(&state) {
+                    self.world.remove_exit(x, y);
+                    self.world.add_wall(x, y);
+                } else {
+                    self.world.remove_wall(x, y);
+                }
+                edited = true;
+            }
+
+            if self.input.is_pressed(r, "toggle_exit") {
+                if self.world.can_exit(&state) {
+                    self.world.remove_exit(x, y);
+                } else {
+                    self.world.remove_wall(x, y);
+                    self.world.add_exit(x, y, 1.0);
+                }
+                edited = true;
+            }
+
+            if self.input.is_pressed(r, "place_start") && self.world.is_open(&state) {
+                self.world.start = (x, y);
+                edited = true;
+            }
+
+            let wheel = self.input.axis(r, "increase_reward", "decrease_reward");
+            if wheel != 0.0 && self.world.can_exit(&state) {
+                let reward = self.world.reward(&state, Action::Exit) + wheel * 0.1;
+                self.world.add_exit(x, y, (reward * 10.0).round() / 10.0);
+                edited = true;
+            }
+        }
+
+        if edited {
+            self.world_changed();
+        }
+    }
+
+    /// Replaces the world with the one stored at `path`; later edits are saved back to the same file.
+    fn load_world(&mut self, path: &str) {
+        match World::load(path) {
+            Ok(world) => {
+                self.world = world;
+                self.path = String::from(path);
+                self.watch_world = true;
+                self.user_policy = None;
+                self.show_user_policy = false;
+                self.hovered = None;
+                self.camera = Game::create_camera();
+                self.reset();
+            }
+            Err(error) => eprintln!("Could not load world from '{}': {}", path, error),
+        }
+    }
+
+    fn world_changed(&mut self) {
+        // A hand-written policy no longer describes the world once the world has been edited.
+        self.user_policy = None;
+        self.show_user_policy = false;
+        self.hovered = None;
+
+        self.reset();
+
+        // A world that was not loaded from a file is saved to a new one, rather than over a file it did not come from.
+        if self.path.is_empty() {
+            self.path = Game::unused_world_path();
+            println!("Saving the world to '{}'", self.path);
+        }
+
+        if let Err(error) = self.world.save(&self.path) {
+            eprintln!("Could not save world to '{}': {}", self.path, error);
+        }
+
+        // The file may not have existed before, in which case it is not being watched yet.
+        self.watch_world = self.world_file.is_none();
+    }
+
+    /// The first of `world.txt`, `world-1.txt`, `world-2.txt`, ... that does not exist yet.
+    fn unused_world_path() -> String {
+        (0..)
+            .map(|index| match index {
+                0 => String::from("world.txt"),
+                _ => format!("world-{}.txt", index),
+            })
+            .find(|path| !Path::new(path).exists())
+            .unwrap()
+    }
+
+    /// Starts watching the world file so edits made outside of the editor show up right away.
+    fn watch_world(&mut self, context: &mut Context) {
+        if !self.watch_world {
+            return;
+        }
+        self.watch_world = false;
+
+        // The built-in world has no file until the editor saves one; until then, nothing is watched.
+        if self.path.is_empty() {
+            return;
+        }
+        self.world_file = context.load(&self.path).ok();
+    }
+
+    fn reset(&mut self) {
+        self.analysis = Game::create_analysis(&self.world, &self.mode);
+        self.restart();
+    }
+
+    /// Performs a single sweep of the current solver.
+    fn step(&mut self) {
+        let previous = self.analysis.values.clone();
+        let mut stable = true;
+
+        // Look so the following isn't technically correct; however, doing it like this makes the visualization cooler!
+        match self.mode {
+            Mode::Value => {
+                self.analysis.values = self.world.value_bellman_update(
+                    self.discount,
+                    self.noise,
+                    &self.analysis.values,
+                    &mut self.analysis.q_values,
+                );
+                self.analysis.policy = self.world.generate_policy(&self.analysis.q_values);
+            }
+            Mode::Policy => {
+                self.analysis.values = self.world.policy_bellman_update(
+                    self.discount,
+                    self.noise,
+                    &self.analysis.policy,
+                    &self.analysis.values,
+                );
+                let (temp, temp_stable) = self.world.policy_improvement(
+                    self.discount,
+                    self.noise,
+                    &self.analysis.policy,
+                    &self.analysis.values,
+                    &mut self.analysis.q_values,
+                );
+                self.analysis.policy = temp;
+                stable = temp_stable;
+            }
+        }
+
+        self.analysis.min_value = Analysis::min(&self.analysis.values);
+        self.analysis.max_value = Analysis::max(&self.analysis.values);
+
+        self.residual = self
+            .analysis
+            .values
+            .iter()
+            .zip(previous.iter())
+            .map(|(current, previous)| (current - previous).abs())
+            .fold(0.0, f32::max);
+        self.iteration += 1;
+        self.converged = stable && self.iteration > 1 && self.residual < self.epsilon;
+
+        self.history.record(self.snapshot());
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            iteration: self.iteration,
+            residual: self.residual,
+            converged: self.converged,
+            analysis: self.analysis.clone(),
+        }
+    }
+
+    /// Displays a previously recorded iteration; stepping from there discards the iterations that followed it.
+    fn seek(&mut self, position: usize) {
+        let snapshot = self.history.seek(position);
+
+        self.iteration = snapshot.iteration;
+        self.residual = snapshot.residual;
+        self.converged = snapshot.converged;
+        self.analysis = snapshot.analysis.clone();
+        self.previous = None;
+        self.paused = true;
+    }
+
+    fn timeline_bounds(&self, screen_width: i32, screen_height: i32) -> Rectangle {
+        Rectangle::new(
+            10.0,
+            screen_height as f32 - 18.0,
+            (screen_width - 80).max(1) as f32,
+            8.0,
+        )
+    }
+
+    fn update_timeline(&mut self, r: &dyn Backend) {
+        if self.input.is_pressed(r, "previous_iteration") && self.history.position() > 0 {
+            self.seek(self.history.position() - 1);
+        }
+
+        if self.input.is_pressed(r, "next_iteration") {
+            self.seek(self.history.position() + 1);
+        }
+
+        if self.input.is_pressed(r, "compare") {
+            self.compare = match self.compare {
+                Some(_) => None,
+                None => Some(self.iteration),
+            };
+        }
+
+        let bounds = self.timeline_bounds(r.get_screen_width(), r.get_screen_height());
+        let mouse = r.get_mouse_position();
+
+        // While editing, clicks belong to the cells instead.
+        if !self.editing && self.input.is_pressed(r, "scrub") {
+            // Be a little forgiving; the bar itself is quite thin.
+            let area = Rectangle::new(bounds.x, bounds.y - 6.0, bounds.width, bounds.height + 12.0);
+            self.scrubbing = area.check_collision_point_rec(mouse);
+        }
+
+        if !self.input.is_down(r, "scrub") {
+            self.scrubbing = false;
+        }
+
+        if self.scrubbing && self.history.len() > 1 {
+            let t = ((mouse.x - bounds.x) / bounds.width).clamp(0.0, 1.0);
+            let position = (t * (self.history.len() - 1) as f32).round() as usize;
+
+            if position != self.history.position() {
+                self.seek(position);
+            }
+        }
+    }
+
+    fn draw_timeline(&self, d: &mut Frame) {
+        let (screen_width, screen_height) = d.screen_size();
+        let bounds = self.timeline_bounds(screen_width, screen_height);
+        let last = (self.history.len() - 1).max(1) as f32;
+        let x = |position: usize| bounds.x + bounds.width * position as f32 / last;
+
+        d.draw_rectangle_rec(bounds, Color::new(255, 255, 255, 55));
+
+        let marker = self
+            .compare
+            .and_then(|iteration| self.history.index_of(iteration));
+        if let Some(position) = marker {
+            d.draw_rectangle(
+                x(position) as i32 - 1,
+                bounds.y as i32 - 4,
+                3,
+                bounds.height as i32 + 8,
+                Color::new(255, 0, 255, 255),
+            );
+        }
+
+        d.draw_rectangle(
+            x(self.history.position()) as i32 - 2,
+            bounds.y as i32 - 4,
+            5,
+            bounds.height as i32 + 8,
+            Color::new(255, 255, 255, 255),
+        );
+    }
+
+    fn run_to_convergence(&mut self) {
+        // Guard against worlds that never settle, e.g. a discount of one without any exits.
+        let limit = self.iteration + 10000;
+
+        while !self.converged && self.iteration < limit {
+            self.step();
+        }
+    }
+
+    fn update_controls(&mut self, r: &dyn Backend) {
+        if self.input.is_pressed(r, "pause") {
+            self.paused = !self.paused;
+        }
+
+        if self.input.is_pressed(r, "step") {
+            self.paused = true;
+            self.step();
+        }
+
+        if self.input.is_pressed(r, "speed_up") {
+            self.speed = (self.speed + 1).min(3);
+        }
+
+        if self.input.is_pressed(r, "slow_down") {
+            self.speed = (self.speed - 1).max(-7);
+        }
+
+        if self.input.is_pressed(r, "reset") {
+            self.reset();
+        }
+
+        if self.input.is_pressed(r, "converge") {
+            self.run_to_convergence();
+        }
+
+        let discount = self.discount;
+        let noise = self.noise;
+        let living_reward = self.world.living_reward;
+
+        if self.input.is_pressed(r, "decrease_discount") {
+            self.discount = Game::adjust(self.discount, -0.05, 0.0, 1.0);
+        }
+        if self.input.is_pressed(r, "increase_discount") {
+            self.discount = Game::adjust(self.discount, 0.05, 0.0, 1.0);
+        }
+        if self.input.is_pressed(r, "decrease_noise") {
+            self.noise = Game::adjust(self.noise, -0.05, 0.0, 1.0);
+        }
+        if self.input.is_pressed(r, "increase_noise") {
+            self.noise = Game::adjust(self.noise, 0.05, 0.0, 1.0);
+        }
+        if self.input.is_pressed(r, "decrease_living_reward") {
+            self.world.living_reward = Game::adjust(living_reward, -0.01, -10.0, 10.0);
+        }
+        if self.input.is_pressed(r, "increase_living_reward") {
+            self.world.living_reward = Game::adjust(living_reward, 0.01, -10.0, 10.0);
+        }
+
+        if self.discount != discount
+            || self.noise != noise
+            || self.world.living_reward != living_reward
+        {
+            self.restart();
+        }
+    }
+
+    /// Nudges a parameter by `step`, rounding away the drift that repeated float additions cause.
+    fn adjust(value: f32, step: f32, min: f32, max: f32) -> f32 {
+        ((value + step) * 100.0)
+            .round()
+            .max(min * 100.0)
+            .min(max * 100.0)
+            / 100.0
+    }
+
+    /// Restarts the solver from the current values, e.g. after one of its parameters changed.
+    fn restart(&mut self) {
+        self.iteration = 0;
+        self.residual = 0.0;
+        self.converged = false;
+        self.ticks = 0;
+        self.agent_ticks = 0;
+        self.history.clear(self.snapshot());
+        self.compare = None;
+        self.previous = None;
+        self.agent.reset(self.world.start);
+    }
+
+    /// How many fixed updates pass between sweeps, and how many sweeps are then performed at once.
+    ///
+    /// The speed is a power of two; negative speeds sweep once every few fixed updates.
+    fn sweep_rate(&self) -> (u32, u32) {
+        (1 << (-self.speed).max(0), 1 << self.speed.max(0))
+    }
+
+    fn sweeps_per_second(&self) -> f32 {
+        TICK_RATE as f32 * 2.0_f32.powi(self.speed)
+    }
+
+    /// Fades every cell from its color in `previous` over the time it takes to reach the next iteration.
+    fn fade_from(&mut self, context: &mut Context, previous: Analysis) {
+        let tweens = match context.tweens() {
+            Some(tweens) => tweens,
+            None => return,
         };
 
-        Game {
-            camera: Camera2D {
-                zoom: 1.0,
-                target: Vector2::new(0.0, 0.0),
-                rotation: 0.0,
-                offset: Vector2::new(0.0, 0.0),
-            },
-            world,
-            analysis,
-            mode,
-            discount,
-            noise,
-            show_policy: false,
-            target: 1. / 5.,
-            accumulator: 0.0,
-        }
-    }
-
-    fn calculate_color(&self, value: f32) -> Color {
-        let color;
-        if value < 0.0 {
-            color = Color::new(
-                rna::remap_range(
-                    value as f64,
-                    self.analysis.min_value as f64,
-                    0.0,
-                    255.0,
-                    0.0,
-                ) as u8,
-                0,
-                0,
-                255,
+        if let Some(fade) = self.fade.take() {
+            tweens.remove(fade);
+        }
+        self.fade = Some(
+            tweens.start(
+                Tween::new(0.0, 1.0, self.sweep_rate().0 as f32 / TICK_RATE as f32)
+                    .easing(Easing::QuadOut),
+            ),
+        );
+        self.previous = Some(previous);
+    }
+
+    /// The name of the first binding of `action`, as shown in the HUD.
+    fn binding_name(&self, action: &str) -> &'static str {
+        self.input
+            .bindings(action)
+            .first()
+            .map_or("unbound", Binding::name)
+    }
+
+    /// Names the bindings of a pair of actions, e.g. "Q/W", as they are currently bound.
+    fn hint(&self, decrease: &str, increase: &str) -> String {
+        format!(
+            "{}/{}",
+            self.binding_name(decrease),
+            self.binding_name(increase)
+        )
+    }
+
+    fn draw_hud(&self, d: &mut Frame) {
+        let mut status = format!(
+            "Iteration {}  Residual {:.5}  Speed {:.1}/s",
+            self.iteration,
+            self.residual,
+            self.sweeps_per_second()
+        );
+
+        if self.converged {
+            status.push_str("  CONVERGED");
+        }
+        if self.paused {
+            status.push_str("  PAUSED");
+        }
+        if let Some(iteration) = self.compare {
+            status.push_str(&format!("  COMPARING WITH {}", iteration));
+        }
+
+        d.draw_text(&status, 10, 10, 10, Color::new(255, 255, 255, 255));
+
+        let parameters = format!(
+            "Discount {:.2} ({})  Noise {:.2} ({})  Living reward {:.2} ({})",
+            self.discount,
+            self.hint("decrease_discount", "increase_discount"),
+            self.noise,
+            self.hint("decrease_noise", "increase_noise"),
+            self.world.living_reward,
+            self.hint("decrease_living_reward", "increase_living_reward")
+        );
+
+        d.draw_text(&parameters, 10, 25, 10, Color::new(255, 255, 255, 255));
+
+        if self.show_agent {
+            let (x, y) = self.world.start;
+            let agent = format!(
+                "Episodes {}  Average return {:.4}  V(start) {:.4}",
+                self.agent.episodes(),
+                self.agent.average_return(),
+                self.analysis.values[y * self.world.width + x]
             );
+
+            d.draw_text(&agent, 10, 40, 10, Color::new(255, 255, 255, 255));
+        }
+    }
+
+    fn draw_inspector(&self, d: &mut Frame) {
+        let (x, y) = match self.hovered {
+            Some(cell) => cell,
+            None => return,
+        };
+
+        let index = y * self.world.width + x;
+        let state = State::new(x, y);
+        let mut lines = vec![format!("Cell ({}, {})", x, y)];
+
+        if !self.world.valid_position(&state) {
+            lines.push(String::from("Wall"));
         } else {
-            color = Color::new(
-                0,
-                rna::remap_range(
-                    value as f64,
-                    0.0,
-                    self.analysis.max_value as f64,
-                    0.0,
-                    255.0,
-                ) as u8,
-                0,
-                255,
+            let action = self.analysis.policy[index];
+
+            lines.push(format!("V = {:.4}", self.analysis.values[index]));
+
+            if !self.world.can_exit(&state) {
+                let q_values = self.analysis.q_values[index];
+                lines.push(format!("Q Up    = {:.4}", q_values[0]));
+                lines.push(format!("Q Right = {:.4}", q_values[1]));
+                lines.push(format!("Q Down  = {:.4}", q_values[2]));
+                lines.push(format!("Q Left  = {:.4}", q_values[3]));
+            }
+
+            lines.push(format!("Action {:?}", action));
+
+            if let Some(transitions) = self.world.transition(&state, action, self.noise) {
+                for (probability, outcome) in transitions {
+                    match outcome {
+                        Action::Move(direction) => {
+                            let target = self.world.move_to(&state, direction);
+                            lines.push(format!(
+                                "  {:.2} {:?} -> ({}, {})",
+                                probability, direction, target.x, target.y
+                            ));
+                        }
+                        _ => lines.push(format!("  {:.2} {:?}", probability, outcome)),
+                    }
+                }
+            }
+        }
+
+        let font_size = 10;
+        let line_height = font_size + 4;
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, font_size))
+            .max()
+            .unwrap_or(0)
+            + 12;
+        let height = lines.len() as i32 * line_height + 8;
+
+        // Keep the panel next to the cursor without letting it leave the screen.
+        let mouse = d.mouse_position();
+        let (screen_width, screen_height) = d.screen_size();
+        let left = (mouse.x as i32 + 16).min(screen_width - width).max(0);
+        let top = (mouse.y as i32 + 16).min(screen_height - height).max(0);
+
+        d.draw_rectangle(left, top, width, height, Color::new(0, 0, 0, 200));
+        d.draw_rectangle_lines(left, top, width, height, Color::new(255, 255, 255, 155));
+
+        for (i, line) in lines.iter().enumerate() {
+            d.draw_text(
+                line,
+                left + 6,
+                top + 4 + i as i32 * line_height,
+                font_size,
+                Color::new(255, 255, 255, 255),
             );
         }
+    }
+
+    fn draw_agent(
+        &self,
+        d: &mut RaylibMode2D<Frame>,
+        size: usize,
+        x_offset: usize,
+        y_offset: usize,
+        alpha: f32,
+    ) {
+        let size = size as f32;
+        let center = |(x, y): (usize, usize)| {
+            Vector2::new(
+                x as f32 * size + size * 0.5 + x_offset as f32,
+                y as f32 * size + size * 0.5 + y_offset as f32,
+            )
+        };
+
+        let start = center(self.world.start);
+        d.draw_circle_lines(
+            start.x as i32,
+            start.y as i32,
+            size * 0.3,
+            Color::new(255, 255, 255, 155),
+        );
+
+        // Slide between cells so that slips caused by noise are easy to follow.
+        let step = if self.paused { 1.0 } else { alpha };
+        let previous = center(self.agent.previous);
+        let current = center(self.agent.position);
+        let position = Vector2::new(
+            previous.x + (current.x - previous.x) * step,
+            previous.y + (current.y - previous.y) * step,
+        );
+
+        d.draw_circle_v(position, size * 0.2, Color::new(41, 173, 255, 255));
+    }
+
+    fn draw_number(
+        &self,
+        d: &mut RaylibMode2D<Frame>,
+        value: f32,
+        center: Vector2,
+        font_size: i32,
+    ) {
+        let text = format!("{:.2}", value);
+        let width = measure_text(&text, font_size);
+
+        d.draw_text(
+            &text,
+            center.x as i32 - width / 2,
+            center.y as i32 - font_size / 2,
+            font_size,
+            Color::new(255, 255, 255, 255),
+        );
+    }
 
-        color
+    fn calculate_color(&self, analysis: &Analysis, value: f32) -> Color {
+        self.color_map.color(
+            value as f64,
+            analysis.min_value as f64,
+            analysis.max_value as f64,
+            self.scaling,
+        )
+    }
+
+    /// The color of whatever `value` picks out of an analysis, partway through fading in from the previous iteration.
+    fn blended_color(&self, fade: Option<f32>, value: impl Fn(&Analysis) -> f32) -> Color {
+        let color = self.calculate_color(&self.analysis, value(&self.analysis));
+
+        match (fade, &self.previous) {
+            (Some(fade), Some(previous)) if fade < 1.0 => {
+                Color::interpolate(self.calculate_color(previous, value(previous)), color, fade)
+            }
+            _ => color,
+        }
+    }
+
+    fn draw_world(&self, d: &mut RaylibMode2D<Frame>, alpha: f32, fade: Option<f32>) {
+        let (screen_width, screen_height) = d.screen_size();
+        let (size, x_offset, y_offset) = self.layout(screen_width, screen_height);
+
+        // Only visit the cells that are actually inside of the viewport.
+        let top_left = screen_to_world_2d(Vector2::new(0.0, 0.0), self.camera);
+        let bottom_right = screen_to_world_2d(
+            Vector2::new(screen_width as f32, screen_height as f32),
+            self.camera,
+        );
+        let visible = |start: f32, end: f32, offset: usize, length: usize| {
+            let start = ((start - offset as f32) / size as f32).floor().max(0.0) as usize;
+            let end = ((end - offset as f32) / size as f32).ceil().max(0.0) as usize;
+            start.min(length)..end.min(length)
+        };
+        let columns = visible(top_left.x, bottom_right.x, x_offset, self.world.width);
+        let rows = visible(top_left.y, bottom_right.y, y_offset, self.world.height);
+
+        let compare = self
+            .compare
+            .and_then(|iteration| self.history.find(iteration));
+
+        for y in rows.clone() {
+            for x in columns.clone() {
+                let state = State::new(x, y);
+                if self.world.valid_position(&state) {
+                    if self.world.can_exit(&state) {
+                        let value = self.world.reward(&state, Action::Exit);
+                        d.draw_rectangle(
+                            x as i32 * size as i32 + x_offset as i32,
+                            y as i32 * size as i32 + y_offset as i32,
+                            size as i32,
+                            size as i32,
+                            self.calculate_color(&self.analysis, value),
+                        );
+                    } else {
+                        self.draw_cell(
+                            d,
+                            fade,
+                            y * self.world.width + x,
+                            x as f32 * size as f32 + x_offset as f32,
+                            y as f32 * size as f32 + y_offset as f32,
+                            size,
+                        );
+                    }
+
+                    let index = y * self.world.width + x;
+                    let x = x as f32 * size as f32 + x_offset as f32;
+                    let y = y as f32 * size as f32 + y_offset as f32;
+
+                    if self.show_policy {
+                        self.draw_policy(
+                            d,
+                            self.analysis.policy[index],
+                            x,
+                            y,
+                            size,
+                            Color::new(255, 255, 255, 155),
+                        );
+                    }
+
+                    if let (true, Some(policy)) = (self.show_user_policy, &self.user_policy) {
+                        self.draw_policy(
+                            d,
+                            policy[index],
+                            x,
+                            y,
+                            size,
+                            Color::new(255, 213, 0, 155),
+                        );
+                    }
+
+                    let font_size = (size as i32 / 8).max(1);
+                    if self.show_numbers && font_size as f32 * self.camera.zoom >= 6.0 {
+                        let size = size as f32;
+
+                        if self.world.can_exit(&state) {
+                            self.draw_number(
+                                d,
+                                self.analysis.values[index],
+                                Vector2::new(x + size * 0.5, y + size * 0.5),
+                                font_size,
+                            );
+                        } else {
+                            // Each number sits at the centroid of its triangle.
+                            let q_values = self.analysis.q_values[index];
+                            let centers = [
+                                Vector2::new(x + size * 0.5, y + size / 6.0),
+                                Vector2::new(x + size * 5.0 / 6.0, y + size * 0.5),
+                                Vector2::new(x + size * 0.5, y + size * 5.0 / 6.0),
+                                Vector2::new(x + size / 6.0, y + size * 0.5),
+                            ];
+
+                            for (value, center) in q_values.iter().zip(centers.iter()) {
+                                self.draw_number(d, *value, *center, font_size);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(compare) = compare {
+            // Highlight every cell whose policy differs between the two iterations.
+            for y in rows.clone() {
+                for x in columns.clone() {
+                    let index = y * self.world.width + x;
+                    if compare.analysis.policy[index] != self.analysis.policy[index] {
+                        d.draw_rectangle_lines_ex(
+                            Rectangle::new(
+                                (x * size + x_offset) as f32,
+                                (y * size + y_offset) as f32,
+                                size as f32,
+                                size as f32,
+                            ),
+                            (size as i32 / 16).max(1),
+                            Color::new(255, 0, 255, 255),
+                        );
+                    }
+                }
+            }
+        }
+
+        if self.show_agent {
+            self.draw_agent(d, size, x_offset, y_offset, alpha);
+        }
+
+        if let (true, Some((x, y))) = (self.editing, self.hovered) {
+            d.draw_rectangle_lines(
+                (x * size + x_offset) as i32,
+                (y * size + y_offset) as i32,
+                size as i32,
+                size as i32,
+                Color::new(255, 255, 255, 255),
+            );
+        }
     }
 
     fn draw_cell(
         &self,
-        d: &mut RaylibMode2D<RaylibDrawHandle>,
+        d: &mut RaylibMode2D<Frame>,
+        fade: Option<f32>,
         index: usize,
         x: f32,
         y: f32,
         size: usize,
     ) {
-        let q_values = self.analysis.q_values[index];
+        let color = |direction: usize| {
+            self.blended_color(fade, |analysis| analysis.q_values[index][direction])
+        };
 
         d.draw_triangle(
             Vector2::new(x as f32, y as f32),
             Vector2::new(x as f32 + size as f32 * 0.5, y as f32 + size as f32 * 0.5),
             Vector2::new(x as f32 + size as f32, y as f32),
-            self.calculate_color(q_values[0]),
+            color(0),
         );
         d.draw_triangle(
             Vector2::new(x as f32 + size as f32, y as f32),
             Vector2::new(x as f32 + size as f32 * 0.5, y as f32 + size as f32 * 0.5),
             Vector2::new(x as f32 + size as f32, y as f32 + size as f32),
-            self.calculate_color(q_values[1]),
+            color(1),
         );
         d.draw_triangle(
             Vector2::new(x as f32 + size as f32, y as f32 + size as f32),
             Vector2::new(x as f32 + size as f32 * 0.5, y as f32 + size as f32 * 0.5),
             Vector2::new(x as f32, y as f32 + size as f32),
-            self.calculate_color(q_values[2]),
+            color(2),
         );
         d.draw_triangle(
             Vector2::new(x as f32, y as f32 + size as f32),
             Vector2::new(x as f32 + size as f32 * 0.5, y as f32 + size as f32 * 0.5),
             Vector2::new(x as f32, y as f32),
-            self.calculate_color(q_values[3]),
+            color(3),
         );
     }
 
     fn draw_policy(
         &self,
-        d: &mut RaylibMode2D<RaylibDrawHandle>,
-        x: usize,
-        y: usize,
-        x_offset: usize,
-        y_offset: usize,
+        d: &mut RaylibMode2D<Frame>,
+        action: Action,
+        x: f32,
+        y: f32,
         size: usize,
+        color: Color,
     ) {
-        if !self.show_policy {
-            return;
-        }
+        let size = size as f32;
 
-        match self.analysis.policy[y * self.world.width + x] {
+        match action {
             Action::Exit => {
-                let padding = (size as f32 * 0.07) as i32;
-                let thickness = (size as f32 * 0.05) as i32;
+                let padding = (size * 0.07) as i32;
+                let thickness = (size * 0.05) as i32;
                 let thickness = 1.max(thickness);
                 d.draw_rectangle_lines_ex(
                     Rectangle::new(
-                        x as f32 * size as f32 + x_offset as f32 + padding as f32,
-                        y as f32 * size as f32 + y_offset as f32 + padding as f32,
-                        size as f32 - padding as f32 * 2.0,
-                        size as f32 - padding as f32 * 2.0,
+                        x + padding as f32,
+                        y + padding as f32,
+                        size - padding as f32 * 2.0,
+                        size - padding as f32 * 2.0,
                     ),
                     thickness,
-                    Color::new(255, 255, 255, 155),
+                    color,
                 )
             }
             Action::Move(direction) => match direction {
                 Direction::Up => {
                     d.draw_triangle(
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.2 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.4 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.8 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.4 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.5 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.1 + y_offset as f32,
-                        ),
-                        Color::new(255, 255, 255, 155),
+                        Vector2::new(x + size * 0.2, y + size * 0.4),
+                        Vector2::new(x + size * 0.8, y + size * 0.4),
+                        Vector2::new(x + size * 0.5, y + size * 0.1),
+                        color,
                     );
                     d.draw_rectangle(
-                        (x as f32 * size as f32 + size as f32 * 0.35 + x_offset as f32).round()
-                            as i32,
-                        (y as f32 * size as f32 + size as f32 * 0.4 + y_offset as f32).round()
-                            as i32,
-                        (size as f32 * 0.3) as i32,
-                        (size as f32 * 0.5) as i32,
-                        Color::new(255, 255, 255, 155),
+                        (x + size * 0.35).round() as i32,
+                        (y + size * 0.4).round() as i32,
+                        (size * 0.3) as i32,
+                        (size * 0.5) as i32,
+                        color,
                     )
                 }
                 Direction::Right => {
                     d.draw_triangle(
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.6 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.2 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.6 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.8 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.9 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.5 + y_offset as f32,
-                        ),
-                        Color::new(255, 255, 255, 155),
+                        Vector2::new(x + size * 0.6, y + size * 0.2),
+                        Vector2::new(x + size * 0.6, y + size * 0.8),
+                        Vector2::new(x + size * 0.9, y + size * 0.5),
+                        color,
                     );
                     d.draw_rectangle(
-                        (x as f32 * size as f32 + size as f32 * 0.1 + x_offset as f32).round()
-                            as i32,
-                        (y as f32 * size as f32 + size as f32 * 0.35 + y_offset as f32).round()
-                            as i32,
-                        (size as f32 * 0.5) as i32,
-                        (size as f32 * 0.3) as i32,
-                        Color::new(255, 255, 255, 155),
+                        (x + size * 0.1).round() as i32,
+                        (y + size * 0.35).round() as i32,
+                        (size * 0.5) as i32,
+                        (size * 0.3) as i32,
+                        color,
                     )
                 }
                 Direction::Down => {
                     d.draw_triangle(
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.2 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.6 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.5 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.9 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.8 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.6 + y_offset as f32,
-                        ),
-                        Color::new(255, 255, 255, 155),
+                        Vector2::new(x + size * 0.2, y + size * 0.6),
+                        Vector2::new(x + size * 0.5, y + size * 0.9),
+                        Vector2::new(x + size * 0.8, y + size * 0.6),
+                        color,
                     );
                     d.draw_rectangle(
-                        (x as f32 * size as f32 + size as f32 * 0.35 + x_offset as f32).round()
-                            as i32,
-                        (y as f32 * size as f32 + size as f32 * 0.1 + y_offset as f32).round()
-                            as i32,
-                        (size as f32 * 0.3) as i32,
-                        (size as f32 * 0.5) as i32,
-                        Color::new(255, 255, 255, 155),
+                        (x + size * 0.35).round() as i32,
+                        (y + size * 0.1).round() as i32,
+                        (size * 0.3) as i32,
+                        (size * 0.5) as i32,
+                        color,
                     )
                 }
                 Direction::Left => {
                     d.draw_triangle(
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.4 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.2 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.1 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.5 + y_offset as f32,
-                        ),
-                        Vector2::new(
-                            x as f32 * size as f32 + size as f32 * 0.4 + x_offset as f32,
-                            y as f32 * size as f32 + size as f32 * 0.8 + y_offset as f32,
-                        ),
-                        Color::new(255, 255, 255, 155),
+                        Vector2::new(x + size * 0.4, y + size * 0.2),
+                        Vector2::new(x + size * 0.1, y + size * 0.5),
+                        Vector2::new(x + size * 0.4, y + size * 0.8),
+                        color,
                     );
                     d.draw_rectangle(
-                        (x as f32 * size as f32 + size as f32 * 0.4 + x_offset as f32).round()
-                            as i32,
-                        (y as f32 * size as f32 + size as f32 * 0.35 + y_offset as f32).round()
-                            as i32,
-                        (size as f32 * 0.5) as i32,
-                        (size as f32 * 0.3) as i32,
-                        Color::new(255, 255, 255, 155),
+                        (x + size * 0.4).round() as i32,
+                        (y + size * 0.35).round() as i32,
+                        (size * 0.5) as i32,
+                        (size * 0.3) as i32,
+                        color,
                     )
                 }
             },
@@ -306,84 +1303,173 @@ impl Game {
 }
 
 impl Core for Game {
-    fn initialize(&mut self, _: &mut RaylibHandle, _: &RaylibThread) {}
-    fn update(&mut self, r: &mut RaylibHandle, _: &RaylibThread) {
-        if r.is_key_pressed(KeyboardKey::KEY_SPACE) {
+    fn initialize(&mut self, _: &mut Context) {}
+    fn update(&mut self, r: &mut Context) {
+        self.watch_world(r);
+
+        if self.input.is_pressed(r, "toggle_policy") {
             self.show_policy = !self.show_policy;
         }
 
-        self.accumulator += r.get_frame_time();
+        if self.input.is_pressed(r, "toggle_user_policy") && self.user_policy.is_some() {
+            self.show_user_policy = !self.show_user_policy;
+        }
 
-        if self.accumulator > self.target {
-            self.accumulator -= self.target;
+        if self.input.is_pressed(r, "toggle_numbers") {
+            self.show_numbers = !self.show_numbers;
+        }
 
-            // Look so the following isn't technically correct; however, doing it like this makes the visualization cooler!
-            match self.mode {
-                Mode::Value => {
-                    self.analysis.values = self.world.value_bellman_update(
-                        self.discount,
-                        self.noise,
-                        &self.analysis.values,
-                        &mut self.analysis.q_values,
-                    );
-                    self.analysis.policy = self.world.generate_policy(&self.analysis.q_values);
-                }
-                Mode::Policy => {
-                    self.analysis.values = self.world.policy_bellman_update(
-                        self.discount,
-                        self.noise,
-                        &self.analysis.policy,
-                        &self.analysis.values,
-                    );
-                    let (temp, _) = self.world.policy_improvement(
-                        self.discount,
-                        self.noise,
-                        &self.analysis.policy,
-                        &self.analysis.values,
-                        &mut self.analysis.q_values,
-                    );
-                    self.analysis.policy = temp;
-                }
+        if self.input.is_pressed(r, "next_color_map") {
+            self.color_map = self.color_map.next();
+        }
+
+        if self.input.is_pressed(r, "toggle_scaling") {
+            self.scaling = match self.scaling {
+                Scaling::Symmetric => Scaling::Independent,
+                Scaling::Independent => Scaling::Symmetric,
+            };
+        }
+
+        if self.input.is_pressed(r, "toggle_legend") {
+            self.show_legend = !self.show_legend;
+        }
+
+        if self.input.is_pressed(r, "toggle_editor") {
+            self.editing = !self.editing;
+        }
+
+        if self.input.is_pressed(r, "toggle_agent") {
+            self.show_agent = !self.show_agent;
+            self.agent.reset(self.world.start);
+        }
+
+        self.hovered = self.cell_at(r, r.get_mouse_position());
+
+        r.watch("iteration", self.iteration);
+        r.watch("residual", self.residual);
+        r.watch("snapshots", self.history.len());
+
+        self.update_timeline(r);
+
+        if !self.scrubbing {
+            self.update_camera(r);
+
+            if self.editing {
+                self.update_editor(r);
             }
+        }
 
-            self.analysis.min_value = Analysis::min(&self.analysis.values);
-            self.analysis.max_value = Analysis::max(&self.analysis.values);
+        self.update_controls(r);
+    }
+    fn fixed_update(&mut self, context: &mut Context) {
+        if self.paused {
+            return;
         }
+
+        // Slowing down takes effect right away, since only the current interval is waited out.
+        let (interval, sweeps) = self.sweep_rate();
+        self.ticks += 1;
+        if self.ticks >= interval {
+            self.ticks = 0;
+
+            let previous = self.analysis.clone();
+            for _ in 0..sweeps {
+                self.step();
+            }
+            self.fade_from(context, previous);
+        }
+
+        if !self.show_agent {
+            return;
+        }
+
+        self.agent_ticks += 1;
+        if self.agent_ticks >= AGENT_TICKS {
+            self.agent_ticks = 0;
+            self.agent.step(
+                &self.world,
+                &self.analysis.policy,
+                self.discount,
+                self.noise,
+                context.random(),
+            );
+        }
+    }
+    fn on_file_dropped(&mut self, path: &Path) {
+        self.load_world(&path.to_string_lossy());
     }
-    fn draw(&self, d: &mut RaylibDrawHandle, _: &RaylibThread) {
-        let mut d = d.begin_mode2D(self.camera);
-        d.clear_background(Color::new(0, 0, 0, 255));
+    fn on_asset_reloaded(&mut self, context: &mut Context, path: &Path) {
+        if self.path.is_empty() || path != Path::new(&self.path) {
+            return;
+        }
 
-        let size = d.get_screen_width() as usize / self.world.width;
-        let size = size.min(d.get_screen_height() as usize / self.world.height);
-        let x_offset = (d.get_screen_width() as usize - self.world.width * size) / 2;
-        let y_offset = (d.get_screen_height() as usize - self.world.height * size) / 2;
+        let world = match self.world_file.and_then(|handle| context.asset(handle)) {
+            Some(data) => World::parse(data),
+            None => return,
+        };
 
-        for y in 0..self.world.height {
-            for x in 0..self.world.width {
-                let state = State::new(x, y);
-                if self.world.valid_position(&state) {
-                    if self.world.can_exit(&state) {
-                        let value = self.world.reward(&state, Action::Exit);
-                        d.draw_rectangle(
-                            x as i32 * size as i32 + x_offset as i32,
-                            y as i32 * size as i32 + y_offset as i32,
-                            size as i32,
-                            size as i32,
-                            self.calculate_color(value),
-                        );
-                    } else {
-                        self.draw_cell(
-                            &mut d,
-                            y * self.world.width + x,
-                            x as f32 * size as f32 + x_offset as f32,
-                            y as f32 * size as f32 + y_offset as f32,
-                            size,
-                        );
-                    }
-                    self.draw_policy(&mut d, x, y, x_offset, y_offset, size)
-                }
+        match world {
+            // Saving an edit touches the file as well, but leaves nothing new to pick up.
+            Ok(world) if world.serialize() == self.world.serialize() => (),
+            Ok(world) => {
+                self.world = world;
+                self.user_policy = None;
+                self.show_user_policy = false;
+                self.hovered = None;
+                self.reset();
             }
+            Err(error) => eprintln!(
+                "Could not reload world from '{}': {}",
+                path.to_string_lossy(),
+                error
+            ),
+        }
+    }
+    fn draw(&self, d: &mut Frame) {
+        d.clear(Color::new(0, 0, 0, 255));
+
+        {
+            // The agent moves once every few fixed updates, so its progress spans all of them.
+            let alpha = (self.agent_ticks as f32 + d.alpha()) / AGENT_TICKS as f32;
+            let fade = self.fade.and_then(|fade| d.tween(fade));
+            let mut d = d.begin_mode2D(self.camera);
+            self.draw_world(&mut d, alpha, fade);
+        }
+
+        self.draw_hud(d);
+        self.draw_timeline(d);
+
+        if self.show_legend {
+            let (screen_width, screen_height) = d.screen_size();
+            let bounds = Rectangle::new(
+                screen_width as f32 - 22.0,
+                10.0,
+                12.0,
+                screen_height as f32 - 20.0,
+            );
+
+            self.color_map.draw_legend(
+                d,
+                bounds,
+                self.analysis.min_value as f64,
+                self.analysis.max_value as f64,
+                self.scaling,
+            );
+        }
+
+        self.draw_inspector(d);
+
+        if self.editing {
+            let text = format!(
+                "EDIT: {} wall, {} exit, {} start, {} reward, {} width, {} height",
+                self.binding_name("toggle_wall"),
+                self.binding_name("toggle_exit"),
+                self.binding_name("place_start"),
+                self.hint("decrease_reward", "increase_reward"),
+                self.hint("shrink_width", "grow_width"),
+                self.hint("shrink_height", "grow_height")
+            );
+            d.draw_text(&text, 10, 55, 10, Color::new(255, 255, 255, 255));
         }
     }
 }
@@ -0,0 +1,158 @@
+use crate::world::{Action, Analysis};
+use std::collections::VecDeque;
+
+pub struct Snapshot {
+    pub iteration: usize,
+    pub residual: f32,
+    pub converged: bool,
+    pub analysis: Analysis,
+}
+
+impl Snapshot {
+    fn size(&self) -> usize {
+        let area = self.analysis.values.len();
+        let cell = std::mem::size_of::<f32>()
+            + std::mem::size_of::<[f32; 4]>()
+            + std::mem::size_of::<Action>();
+
+        std::mem::size_of::<Snapshot>() + area * cell
+    }
+}
+
+/// Keeps a snapshot of every solver iteration, forgetting the oldest ones once `memory_limit` bytes are in use.
+pub struct History {
+    snapshots: VecDeque<Snapshot>,
+    position: usize,
+    capacity: usize,
+    memory_limit: usize,
+}
+
+impl History {
+    pub fn new(memory_limit: usize, snapshot: Snapshot) -> Self {
+        let mut history = History {
+            snapshots: VecDeque::new(),
+            position: 0,
+            capacity: 1,
+            memory_limit,
+        };
+        history.clear(snapshot);
+
+        history
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn current(&self) -> &Snapshot {
+        &self.snapshots[self.position]
+    }
+
+    pub fn index_of(&self, iteration: usize) -> Option<usize> {
+        self.snapshots
+            .iter()
+            .position(|snapshot| snapshot.iteration == iteration)
+    }
+
+    pub fn find(&self, iteration: usize) -> Option<&Snapshot> {
+        self.index_of(iteration).map(|index| &self.snapshots[index])
+    }
+
+    /// Forgets every snapshot and starts over from `snapshot`.
+    pub fn clear(&mut self, snapshot: Snapshot) {
+        self.capacity = (self.memory_limit / snapshot.size()).max(2);
+        self.snapshots.clear();
+        self.snapshots.push_back(snapshot);
+        self.position = 0;
+    }
+
+    /// Records a new snapshot after the current one; anything that came after the current snapshot is discarded.
+    pub fn record(&mut self, snapshot: Snapshot) {
+        self.snapshots.truncate(self.position + 1);
+        self.snapshots.push_back(snapshot);
+
+        while self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+
+        self.position = self.snapshots.len() - 1;
+    }
+
+    pub fn seek(&mut self, position: usize) -> &Snapshot {
+        self.position = position.min(self.snapshots.len() - 1);
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(iteration: usize) -> Snapshot {
+        Snapshot {
+            iteration,
+            residual: 0.0,
+            converged: false,
+            analysis: Analysis {
+                policy: vec![Action::None; 4],
+                values: vec![iteration as f32; 4],
+                q_values: vec![[0.0; 4]; 4],
+                min_value: 0.0,
+                max_value: 0.0,
+            },
+        }
+    }
+
+    fn iterations(history: &History) -> Vec<usize> {
+        history
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.iteration)
+            .collect()
+    }
+
+    #[test]
+    fn recording_after_seeking_back_discards_later_snapshots() {
+        let mut history = History::new(usize::MAX, snapshot(0));
+        for iteration in 1..5 {
+            history.record(snapshot(iteration));
+        }
+        assert_eq!(history.position(), 4);
+
+        assert_eq!(history.seek(1).iteration, 1);
+        history.record(snapshot(10));
+
+        assert_eq!(iterations(&history), vec![0, 1, 10]);
+        assert_eq!(history.position(), 2);
+        assert!(history.find(3).is_none());
+        assert_eq!(history.seek(99).iteration, 10);
+    }
+
+    #[test]
+    fn the_oldest_snapshots_are_forgotten_beyond_the_memory_limit() {
+        let size = snapshot(0).size();
+
+        let mut history = History::new(size * 3, snapshot(0));
+        for iteration in 1..6 {
+            history.record(snapshot(iteration));
+        }
+
+        assert_eq!(iterations(&history), vec![3, 4, 5]);
+        assert_eq!(history.position(), 2);
+        assert_eq!(history.index_of(4), Some(1));
+
+        // Stepping back needs at least one earlier snapshot, however little memory there is.
+        let mut history = History::new(0, snapshot(0));
+        history.record(snapshot(1));
+        history.record(snapshot(2));
+        assert_eq!(iterations(&history), vec![1, 2]);
+
+        history.clear(snapshot(7));
+        assert_eq!(iterations(&history), vec![7]);
+        assert_eq!(history.position(), 0);
+    }
+}
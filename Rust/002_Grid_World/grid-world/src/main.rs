@@ -1,15 +1,31 @@
+mod agent;
 mod game;
+mod history;
 mod world;
 use game::Game;
 use rna::*;
+use std::path::Path;
 
-fn main() {
-    let mut config = AppConfig::new();
+fn main() -> Result<(), RnaError> {
+    let mut config = AppConfig::builder()
+        .title("Grid World")
+        .size(640, 360)
+        .vsync(true)
+        // The solver and the agent advance in fixed updates.
+        .fixed_timestep(1. / game::TICK_RATE as f32);
 
-    config.title = "Grid World";
-    config.window_size = (640, 360);
-    config.vsync_enabled = true;
-    config.core = Some(Box::new(Game::new(std::env::args())));
+    // Window settings, as well as recording and replaying input, can be changed without recompiling.
+    if Path::new("window.txt").exists() {
+        config = config.load("window.txt")?;
+    }
 
-    App::build(config).run();
+    let game = Game::new(std::env::args());
+    // A seed on the command line repeats an earlier run.
+    if let Some(seed) = game.seed() {
+        config = config.seed(seed);
+    }
+
+    config.core(game).build()?.run();
+
+    Ok(())
 }
@@ -9,7 +9,7 @@ impl State {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Direction {
     Up = 0,
     Down = 1,
@@ -24,7 +24,7 @@ const DIRECTIONS: [Direction; 4] = [
     Direction::Left,
 ];
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Action {
     None,
     Move(Direction),
@@ -36,6 +36,8 @@ pub struct World {
     pub height: usize,
     pub board: Vec<usize>,
     pub exits: Vec<Option<f32>>,
+    pub living_reward: f32,
+    pub start: (usize, usize),
 }
 
 impl World {
@@ -45,19 +47,27 @@ impl World {
             height,
             board: vec![0; width * height],
             exits: vec![None; width * height],
+            living_reward: 0.0,
+            start: (0, height.saturating_sub(1)),
         }
     }
 
     pub fn load(path: &str) -> Result<World, Box<dyn std::error::Error>> {
+        let data = std::fs::read_to_string(path)?;
+
+        World::parse(&data)
+    }
+
+    pub fn parse(data: &str) -> Result<World, Box<dyn std::error::Error>> {
         let mut width = 0;
         let mut height = 0;
         let mut walls = Vec::new();
         let mut exits = Vec::new();
-
-        let data = std::fs::read_to_string(path)?;
+        let mut living_reward = 0.0;
+        let mut start = None;
 
         let lines = data.split("\n");
-        for line in lines {
+        for (index, line) in lines.enumerate() {
             let line = line.trim();
             if line.starts_with("#") {
                 continue;
@@ -142,9 +152,44 @@ impl World {
                 exits.push((x, y, reward));
                 continue;
             }
+
+            if line.starts_with("Start") {
+                let mut temp = line.split_whitespace();
+                temp.next();
+                let data = temp.next().ok_or("Expected more than one entry.")?;
+                let values: Vec<&str> = data.split(",").collect();
+
+                let x = values.first().ok_or("Could not get x.").and_then(|value| {
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| "Could not parse x as usize.")
+                })?;
+
+                let y = values.get(1).ok_or("Could not get y").and_then(|value| {
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| "Cound not parse y as usize.")
+                })?;
+
+                start = Some((x, y, index + 1));
+                continue;
+            }
+
+            if line.starts_with("LivingReward") {
+                let mut temp = line.split_whitespace();
+                temp.next();
+                let data = temp.next().ok_or("Expected more than one entry.")?;
+
+                living_reward = data
+                    .parse::<f32>()
+                    .map_err(|_| "Could not parse living reward as f32.")?;
+
+                continue;
+            }
         }
 
         let mut world = World::new(width, height);
+        world.living_reward = living_reward;
 
         for wall in walls {
             world.add_wall(wall.0, wall.1);
@@ -153,9 +198,224 @@ impl World {
             world.add_exit(exit.0, exit.1, exit.2);
         }
 
+        if let Some((x, y, number)) = start {
+            if x >= width || y >= height {
+                return Err(format!(
+                    "Line {}: Start {},{} is outside of the world.",
+                    number, x, y
+                )
+                .into());
+            }
+
+            if !world.is_open(&State::new(x, y)) {
+                return Err(format!(
+                    "Line {}: Start {},{} must not be a wall or an exit.",
+                    number, x, y
+                )
+                .into());
+            }
+
+            world.start = (x, y);
+        }
+
         Ok(world)
     }
 
+    pub fn load_policy(&self, path: &str) -> Result<Vec<Action>, Box<dyn std::error::Error>> {
+        let data = std::fs::read_to_string(path)?;
+
+        self.parse_policy(&data)
+    }
+
+    /// Reads a policy that gives every state of this world exactly one action, either as "Policy x,y Up" entries
+    /// or as an arrow grid.
+    pub fn parse_policy(&self, data: &str) -> Result<Vec<Action>, Box<dyn std::error::Error>> {
+        let mut policy: Vec<Option<Action>> = vec![None; self.area()];
+        let mut rows = 0;
+
+        for (index, line) in data.split("\n").enumerate() {
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            let entries = if line.starts_with("Policy") {
+                vec![World::parse_policy_entry(line)
+                    .map_err(|error| format!("Line {}: {}", number, error))?]
+            } else {
+                // Anything else is a row of an arrow grid, e.g. "> > > E".
+                let row = World::parse_policy_row(line)
+                    .map_err(|error| format!("Line {}: {}", number, error))?;
+                if rows >= self.height || row.len() != self.width {
+                    return Err(format!(
+                        "Line {}: The policy grid does not match the dimensions of the world.",
+                        number
+                    )
+                    .into());
+                }
+
+                rows += 1;
+                row.into_iter()
+                    .enumerate()
+                    .map(|(x, action)| (x, rows - 1, action))
+                    .collect()
+            };
+
+            for (x, y, action) in entries {
+                if x >= self.width || y >= self.height {
+                    return Err(format!(
+                        "Line {}: Policy {},{} is outside of the world.",
+                        number, x, y
+                    )
+                    .into());
+                }
+
+                let entry = &mut policy[y * self.width + x];
+                if entry.is_some() {
+                    return Err(format!(
+                        "Line {}: Policy {},{} has already been given.",
+                        number, x, y
+                    )
+                    .into());
+                }
+                *entry = Some(action);
+            }
+        }
+
+        if rows != 0 && rows != self.height {
+            return Err("The policy grid does not match the dimensions of the world.".into());
+        }
+
+        // Walls and exits are fixed by the world, but every other state needs a move.
+        let mut actions = Vec::with_capacity(self.area());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let state = State::new(x, y);
+
+                if !self.valid_position(&state) {
+                    actions.push(Action::None);
+                    continue;
+                }
+
+                if self.can_exit(&state) {
+                    actions.push(Action::Exit);
+                    continue;
+                }
+
+                match policy[y * self.width + x] {
+                    Some(Action::Move(direction)) => actions.push(Action::Move(direction)),
+                    Some(_) => return Err(format!("Policy {},{} must be a move.", x, y).into()),
+                    None => return Err(format!("Policy {},{} is missing.", x, y).into()),
+                }
+            }
+        }
+
+        Ok(actions)
+    }
+
+    /// Reads a "Policy x,y Up" entry.
+    fn parse_policy_entry(
+        line: &str,
+    ) -> Result<(usize, usize, Action), Box<dyn std::error::Error>> {
+        let mut temp = line.split_whitespace();
+        temp.next();
+        let data = temp.next().ok_or("Expected more than one entry.")?;
+        let values: Vec<&str> = data.split(",").collect();
+
+        let x = values.first().ok_or("Could not get x.").and_then(|value| {
+            value
+                .parse::<usize>()
+                .map_err(|_| "Could not parse x as usize.")
+        })?;
+
+        let y = values.get(1).ok_or("Could not get y").and_then(|value| {
+            value
+                .parse::<usize>()
+                .map_err(|_| "Cound not parse y as usize.")
+        })?;
+
+        let action = temp.next().ok_or("Could not get action.")?;
+        let action = match action.to_lowercase().as_str() {
+            "up" => Action::Move(Direction::Up),
+            "right" => Action::Move(Direction::Right),
+            "down" => Action::Move(Direction::Down),
+            "left" => Action::Move(Direction::Left),
+            "exit" => Action::Exit,
+            _ => return Err(format!("Could not parse '{}' as an action.", action).into()),
+        };
+
+        Ok((x, y, action))
+    }
+
+    /// Reads a row of an arrow grid: ^ > v < for moves, E for exits and . for walls.
+    fn parse_policy_row(line: &str) -> Result<Vec<Action>, Box<dyn std::error::Error>> {
+        let mut row = Vec::new();
+        for symbol in line.chars().filter(|c| !c.is_whitespace()) {
+            row.push(match symbol {
+                '^' => Action::Move(Direction::Up),
+                '>' => Action::Move(Direction::Right),
+                'v' => Action::Move(Direction::Down),
+                '<' => Action::Move(Direction::Left),
+                'E' => Action::Exit,
+                '.' => Action::None,
+                _ => return Err(format!("Could not parse '{}' as an action.", symbol).into()),
+            });
+        }
+
+        Ok(row)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.serialize())?;
+
+        Ok(())
+    }
+
+    /// The world in the same format `parse` reads.
+    pub fn serialize(&self) -> String {
+        let mut data = format!("Dimension {},{}\n", self.width, self.height);
+
+        data.push_str(&format!("Start {},{}\n", self.start.0, self.start.1));
+
+        if self.living_reward != 0.0 {
+            data.push_str(&format!("LivingReward {}\n", self.living_reward));
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.board[y * self.width + x] != 0 {
+                    data.push_str(&format!("Wall {},{}\n", x, y));
+                }
+            }
+        }
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(reward) = self.exits[y * self.width + x] {
+                    data.push_str(&format!("Exit {},{},{}\n", x, y, reward));
+                }
+            }
+        }
+
+        data
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let mut world = World::new(width, height);
+        world.living_reward = self.living_reward;
+        world.start = (self.start.0.min(width - 1), self.start.1.min(height - 1));
+
+        for y in 0..self.height.min(height) {
+            for x in 0..self.width.min(width) {
+                world.board[y * width + x] = self.board[y * self.width + x];
+                world.exits[y * width + x] = self.exits[y * self.width + x];
+            }
+        }
+
+        *self = world;
+        self.relocate_start();
+    }
+
     pub fn area(&self) -> usize {
         self.width * self.height
     }
@@ -164,12 +424,44 @@ impl World {
         if let Some(target) = self.board.get_mut(y * self.width + x) {
             *target = 1;
         }
+        self.relocate_start();
     }
 
     pub fn add_exit(&mut self, x: usize, y: usize, reward: f32) {
         if let Some(target) = self.exits.get_mut(y * self.width + x) {
             *target = Some(reward);
         }
+        self.relocate_start();
+    }
+
+    /// Moves the start to the open cell closest to the bottom left corner once it is covered by a wall or an exit.
+    fn relocate_start(&mut self) {
+        if self.is_open(&State::new(self.start.0, self.start.1)) {
+            return;
+        }
+
+        let width = self.width;
+        let open = (0..self.height)
+            .rev()
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .find(|&(x, y)| self.is_open(&State::new(x, y)));
+
+        // A world without a single open cell keeps its start; there is nowhere for an agent to go anyway.
+        if let Some(start) = open {
+            self.start = start;
+        }
+    }
+
+    pub fn remove_wall(&mut self, x: usize, y: usize) {
+        if let Some(target) = self.board.get_mut(y * self.width + x) {
+            *target = 0;
+        }
+    }
+
+    pub fn remove_exit(&mut self, x: usize, y: usize) {
+        if let Some(target) = self.exits.get_mut(y * self.width + x) {
+            *target = None;
+        }
     }
 
     pub fn valid_position(&self, state: &State) -> bool {
@@ -179,6 +471,11 @@ impl World {
         false
     }
 
+    /// Whether an agent could stand on this cell, i.e. it is neither a wall nor an exit.
+    pub fn is_open(&self, state: &State) -> bool {
+        self.valid_position(state) && !self.can_exit(state)
+    }
+
     pub fn can_exit(&self, state: &State) -> bool {
         if let Some(target) = self.exits.get(state.y * self.width + state.x) {
             return target.is_some();
@@ -225,7 +522,7 @@ impl World {
                 }
                 0.0
             }
-            Action::Move(_) => 0.0,
+            Action::Move(_) => self.living_reward,
             Action::None => 0.0,
         }
     }
@@ -403,6 +700,17 @@ impl World {
     }
 
     pub fn value_iteration(&mut self, discount: f32, noise: f32, epsilon: f32) -> Vec<Action> {
+        let (_, q_values) = self.optimal_values(discount, noise, epsilon);
+
+        self.generate_policy(&q_values)
+    }
+
+    pub fn optimal_values(
+        &self,
+        discount: f32,
+        noise: f32,
+        epsilon: f32,
+    ) -> (Vec<f32>, Vec<[f32; 4]>) {
         let mut values = vec![0.0; self.area()];
         let mut q_values = vec![[0.0; 4]; self.area()];
 
@@ -428,14 +736,14 @@ impl World {
             }
         }
 
-        self.generate_policy(&q_values)
+        (values, q_values)
     }
 
     pub fn policy_bellman_update(
-        &mut self,
+        &self,
         discount: f32,
         noise: f32,
-        policy: &Vec<Action>,
+        policy: &[Action],
         values: &Vec<f32>,
     ) -> Vec<f32> {
         let mut result = vec![0.0; values.len()];
@@ -453,11 +761,11 @@ impl World {
     }
 
     pub fn policy_evaluation(
-        &mut self,
+        &self,
         discount: f32,
         noise: f32,
         epsilon: f32,
-        policy: &Vec<Action>,
+        policy: &[Action],
         values: &Vec<f32>,
     ) -> Vec<f32> {
         let mut result = values.clone();
@@ -465,7 +773,7 @@ impl World {
         // Loop until convergence.
         loop {
             iterations += 1;
-            let temp = self.policy_bellman_update(discount, noise, &policy, &result);
+            let temp = self.policy_bellman_update(discount, noise, policy, &result);
             let deltas = temp.iter().enumerate().map(|(i, v)| *v - result[i]);
 
             let mut max_delta = f32::MIN;
@@ -567,6 +875,7 @@ impl World {
     }
 }
 
+#[derive(Clone)]
 pub struct Analysis {
     pub policy: Vec<Action>,
     pub values: Vec<f32>,
@@ -598,3 +907,160 @@ impl Analysis {
         max_value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x2 world with a wall in the middle of the top row and an exit in its top right corner.
+    fn small_world() -> World {
+        let mut world = World::new(3, 2);
+        world.add_wall(1, 0);
+        world.add_exit(2, 0, 1.0);
+        world
+    }
+
+    #[test]
+    fn saved_worlds_load_back_unchanged() {
+        let mut world = small_world();
+        world.add_exit(0, 1, -0.5);
+        world.living_reward = -0.04;
+        world.start = (1, 1);
+
+        let path =
+            std::env::temp_dir().join(format!("grid-world-{}-world.txt", std::process::id()));
+        let path = path.to_string_lossy();
+        world.save(&path).unwrap();
+        let loaded = World::load(&path).unwrap();
+        std::fs::remove_file(path.as_ref()).unwrap();
+
+        assert_eq!((loaded.width, loaded.height), (3, 2));
+        assert_eq!(loaded.board, world.board);
+        assert_eq!(loaded.exits, world.exits);
+        assert_eq!(loaded.living_reward, -0.04);
+        assert_eq!(loaded.start, (1, 1));
+        assert_eq!(loaded.serialize(), world.serialize());
+    }
+
+    #[test]
+    fn resizing_keeps_what_still_fits() {
+        let mut world = small_world();
+        world.start = (2, 1);
+
+        world.resize(2, 3);
+        assert_eq!((world.width, world.height), (2, 3));
+        assert!(!world.valid_position(&State::new(1, 0)));
+        assert!(world.exits.iter().all(Option::is_none));
+        assert_eq!(world.start, (1, 1));
+
+        world.resize(4, 3);
+        assert_eq!(world.area(), 12);
+        assert!(!world.valid_position(&State::new(1, 0)));
+        assert!(world.valid_position(&State::new(3, 2)));
+    }
+
+    #[test]
+    fn worlds_without_a_start_begin_in_the_bottom_left_corner() {
+        let world = World::parse("# A comment\nDimension 4,3\nWall 1,1\nExit 3,0,1\n").unwrap();
+
+        assert_eq!(world.start, (0, 2));
+        assert_eq!(world.living_reward, 0.0);
+        assert!(!world.valid_position(&State::new(1, 1)));
+        assert!(world.can_exit(&State::new(3, 0)));
+        assert!(World::parse("Dimension 4,3\nStart 1\n").is_err());
+        assert!(World::parse("Dimension 4,3\nLivingReward lots\n").is_err());
+    }
+
+    #[test]
+    fn starts_must_be_open_cells() {
+        let error = World::parse("Dimension 3,2\nStart 3,1\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Line 2: Start 3,1 is outside of the world."
+        );
+
+        let error = World::parse("Dimension 3,2\nWall 1,0\n\nStart 1,0\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Line 4: Start 1,0 must not be a wall or an exit."
+        );
+        assert!(World::parse("Dimension 3,2\nExit 2,0,1\nStart 2,0\n").is_err());
+    }
+
+    #[test]
+    fn covering_the_start_moves_it() {
+        let mut world = small_world();
+        assert_eq!(world.start, (0, 1));
+
+        world.add_wall(0, 1);
+        assert_eq!(world.start, (1, 1));
+
+        world.add_exit(1, 1, 1.0);
+        assert_eq!(world.start, (2, 1));
+
+        // Shrinking clamps the start onto the exit, which pushes it along again.
+        world.resize(2, 2);
+        assert_eq!(world.start, (0, 0));
+    }
+
+    #[test]
+    fn policies_are_read_from_grids_and_entries() {
+        let world = small_world();
+        let up = Action::Move(Direction::Up);
+        let right = Action::Move(Direction::Right);
+
+        let grid = world.parse_policy("# A comment\n^ . E\n> > ^\n").unwrap();
+        assert_eq!(grid, vec![up, Action::None, Action::Exit, right, right, up]);
+
+        let entries = world
+            .parse_policy("Policy 0,0 Up\nPolicy 0,1 right\nPolicy 1,1 Right\nPolicy 2,1 Up\n")
+            .unwrap();
+        assert_eq!(entries, grid);
+    }
+
+    #[test]
+    fn policies_must_cover_every_state_once() {
+        let world = small_world();
+
+        let missing = world.parse_policy("Policy 0,0 Up\nPolicy 0,1 Right\nPolicy 2,1 Up\n");
+        assert_eq!(missing.unwrap_err().to_string(), "Policy 1,1 is missing.");
+
+        let duplicate = world.parse_policy("^ . E\n> > ^\nPolicy 0,0 Right\n");
+        assert_eq!(
+            duplicate.unwrap_err().to_string(),
+            "Line 3: Policy 0,0 has already been given."
+        );
+
+        let short = world.parse_policy("^ . E\n");
+        assert!(short.is_err());
+    }
+
+    #[test]
+    fn invalid_policy_entries_report_their_line() {
+        let world = small_world();
+
+        let action = world.parse_policy("Policy 0,0 Up\nPolicy 0,1 Sideways\n");
+        assert_eq!(
+            action.unwrap_err().to_string(),
+            "Line 2: Could not parse 'Sideways' as an action."
+        );
+
+        let symbol = world.parse_policy("^ . E\n> ? ^\n");
+        assert_eq!(
+            symbol.unwrap_err().to_string(),
+            "Line 2: Could not parse '?' as an action."
+        );
+
+        let outside = world.parse_policy("\nPolicy 3,1 Up\n");
+        assert_eq!(
+            outside.unwrap_err().to_string(),
+            "Line 2: Policy 3,1 is outside of the world."
+        );
+
+        let exit =
+            world.parse_policy("Policy 0,0 Exit\nPolicy 0,1 Up\nPolicy 1,1 Up\nPolicy 2,1 Up\n");
+        assert_eq!(exit.unwrap_err().to_string(), "Policy 0,0 must be a move.");
+    }
+}
@@ -1,24 +1,121 @@
 use raylib::prelude::*;
+use std::os::raw::{c_int, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
 use super::app_config::AppConfig;
+use super::assets::Assets;
+use super::backend::{Backend, RaylibBackend};
+use super::capture::Capture;
+use super::context::Frame;
 use super::core::Core;
+use super::debug::{DebugOverlay, Watches};
+use super::error::RnaError;
+use super::random::Random;
+use super::replay::Replay;
+use super::runner::{Runner, Services};
+use super::scene::SceneStack;
+use super::timestep::FixedTimestep;
+use super::tween::Tweens;
+use super::viewport::{self, Viewport};
+
+static BUILT: AtomicBool = AtomicBool::new(false);
+
+// GLFW is bundled with raylib, but raylib-sys does not generate bindings for it.
+extern "C" {
+    fn glfwInit() -> c_int;
+    fn glfwGetPrimaryMonitor() -> *mut c_void;
+    fn glfwTerminate();
+}
+
+/// Finds out whether raylib will be able to open a window, which it cannot do without a display.
+///
+/// raylib-rs panics when the window fails to open, so the two checks raylib starts with are made up front instead.
+/// Initializing GLFW twice is harmless; raylib simply continues with the existing instance.
+fn check_display() -> Result<(), RnaError> {
+    unsafe {
+        if glfwInit() == 0 {
+            return Err(RnaError::WindowCreation(String::from(
+                "the windowing system could not be initialized",
+            )));
+        }
+
+        if glfwGetPrimaryMonitor().is_null() {
+            glfwTerminate();
+            return Err(RnaError::WindowCreation(String::from(
+                "there is no display to open a window on",
+            )));
+        }
+    }
+
+    Ok(())
+}
 
 pub struct App {
-    rl: RaylibHandle,
-    thread: RaylibThread,
-    core: Box<dyn Core>,
+    // Declared before `backend` so textures and fonts are unloaded before the window closes.
+    runner: Runner,
+    target: Option<RenderTexture2D>,
+    backend: RaylibBackend,
+    core: SceneStack,
+    capture: Capture,
+    screenshot_key: Option<KeyboardKey>,
+    record_key: Option<KeyboardKey>,
+    record_on_start: bool,
+    overlay: DebugOverlay,
+    overlay_key: Option<KeyboardKey>,
+    fullscreen_key: Option<KeyboardKey>,
+    max_size: Option<(i32, i32)>,
+    letterbox_color: Color,
 }
 
 impl App {
-    pub fn build(config: AppConfig) -> Self {
-        if config.core.is_none() {
-            panic!("An RNA Core was not present; could not create application.");
+    pub fn build(config: AppConfig) -> Result<Self, RnaError> {
+        let core = config.core.ok_or(RnaError::MissingCore)?;
+
+        let (width, height) = config.window_size;
+        if width <= 0 || height <= 0 {
+            return Err(RnaError::InvalidWindowSize(width, height));
+        }
+
+        if config.title.contains('\0') {
+            return Err(RnaError::InvalidTitle(config.title));
+        }
+
+        let sizes = [config.min_size, config.max_size, config.virtual_resolution];
+        for (width, height) in sizes.iter().flatten() {
+            if *width <= 0 || *height <= 0 {
+                return Err(RnaError::InvalidWindowSize(*width, *height));
+            }
+        }
+
+        let icon = match &config.icon {
+            Some(path) => Some(
+                Image::load_image(path)
+                    .map_err(|error| RnaError::InvalidIcon(path.clone(), error))?,
+            ),
+            None => None,
+        };
+
+        let replay = match (&config.replay_input, &config.record_input) {
+            (Some(path), _) => Replay::play(path)
+                .map_err(|error| RnaError::InvalidRecording(path.clone(), error.to_string()))?,
+            (None, Some(path)) => Replay::record(path),
+            (None, None) => Replay::Off,
+        };
+
+        if BUILT.swap(true, Ordering::SeqCst) {
+            return Err(RnaError::AlreadyRunning);
+        }
+
+        if let Err(error) = check_display() {
+            BUILT.store(false, Ordering::SeqCst);
+            return Err(error);
         }
 
         let mut builder = raylib::init();
 
-        builder.title(config.title);
-        builder.size(config.window_size.0, config.window_size.1);
+        builder.title(&config.title);
+        builder.size(width, height);
 
         if config.vsync_enabled {
             builder.vsync();
@@ -32,23 +129,198 @@ impl App {
             builder.undecorated();
         }
 
-        let (rl, thread) = builder.build();
+        if config.fullscreen {
+            builder.fullscreen();
+        }
+
+        if config.msaa_4x {
+            builder.msaa_4x();
+        }
+
+        // raylib-rs has no builder methods for these, but raylib keeps adding up flags until the window opens.
+        let mut flags = 0;
+        if config.high_dpi {
+            flags |= ffi::ConfigFlag::FLAG_WINDOW_HIGHDPI as u32;
+        }
+        if config.run_unfocused {
+            flags |= ffi::ConfigFlag::FLAG_WINDOW_ALWAYS_RUN as u32;
+        }
+        unsafe {
+            ffi::SetConfigFlags(flags);
+        }
+
+        let (mut rl, thread) = builder.build();
+
+        if let Some(fps) = config.target_fps {
+            rl.set_target_fps(fps);
+        }
+
+        if let Some(icon) = icon {
+            rl.set_window_icon(&icon);
+        }
+
+        if let Some((x, y)) = config.position {
+            rl.set_window_position(x, y);
+        }
 
-        App {
-            rl,
-            thread,
-            core: config.core.unwrap(),
+        if let Some((width, height)) = config.min_size {
+            rl.set_window_min_size(width, height);
         }
+
+        let scale_mode = config.scale_mode;
+        let viewport = config
+            .virtual_resolution
+            .map(|(width, height)| Viewport::new(width, height, scale_mode));
+        let target = match config.virtual_resolution {
+            Some((width, height)) => Some(
+                rl.load_render_texture(&thread, width as u32, height as u32)
+                    .map_err(RnaError::WindowCreation)?,
+            ),
+            None => None,
+        };
+
+        Ok(App {
+            runner: Runner::new(
+                Services {
+                    watches: Watches::new(config.debug_overlay),
+                    assets: Assets::new(config.asset_poll_interval),
+                    replay,
+                    tweens: Tweens::new(),
+                    random: config.seed.map_or_else(Random::from_entropy, Random::new),
+                },
+                FixedTimestep::new(config.fixed_timestep, config.max_fixed_updates),
+            ),
+            target,
+            backend: RaylibBackend {
+                rl,
+                thread,
+                input: None,
+                viewport,
+            },
+            core: SceneStack::new(core),
+            capture: Capture::new(
+                &config.capture_directory,
+                config.capture_format,
+                config.capture_frame_rate,
+                config.capture_duration,
+            ),
+            screenshot_key: config.screenshot_key,
+            record_key: config.record_key,
+            record_on_start: config.record_on_start,
+            overlay: DebugOverlay::new(config.debug_overlay, config.frame_time_samples),
+            overlay_key: config.debug_overlay_key,
+            fullscreen_key: config.fullscreen_key,
+            max_size: config.max_size,
+            letterbox_color: config.letterbox_color,
+        })
     }
 
     pub fn run(&mut self) -> &mut Self {
-        self.core.initialize(&mut self.rl, &self.thread);
-        while !self.rl.window_should_close() {
-            self.core.update(&mut self.rl, &self.thread);
-            let mut d = self.rl.begin_drawing(&self.thread);
-            self.core.draw(&mut d, &self.thread);
+        self.runner.initialize(&mut self.core, &mut self.backend);
+        // Printed so that a run can be repeated; a replayed session uses the seed it was recorded with.
+        println!("Random seed: {}", self.runner.services.random.seed());
+
+        if self.record_on_start {
+            self.capture.start();
+        }
+
+        while !self.backend.window_should_close() && !self.core.is_empty() {
+            // Recorded input is captured from, and replayed input stands in for, the real input.
+            self.backend.input = None;
+            self.backend.input = self.runner.services.replay.next_frame(&mut self.backend);
+
+            self.limit_window_size();
+
+            if self.pressed(self.overlay_key) {
+                self.overlay.toggle(&mut self.runner.services.watches);
+            }
+
+            if self.pressed(self.fullscreen_key) {
+                self.backend.rl.toggle_fullscreen();
+            }
+
+            let screenshot = self.pressed(self.screenshot_key);
+            if self.pressed(self.record_key) {
+                if self.capture.is_recording() {
+                    self.capture.stop();
+                } else {
+                    self.capture.start();
+                }
+            }
+
+            let started = Instant::now();
+            let alpha = self.runner.update(&mut self.core, &mut self.backend);
+            let update_time = started.elapsed();
+
+            let started = Instant::now();
+            let screen_size = (
+                self.backend.get_screen_width(),
+                self.backend.get_screen_height(),
+            );
+            let mouse_position = self.backend.get_mouse_position();
+            let mut d = self.backend.rl.begin_drawing(&self.backend.thread);
+            if let Some(target) = &self.target {
+                unsafe {
+                    ffi::BeginTextureMode(*target.as_ref());
+                }
+            }
+            self.core.draw(
+                &mut Frame::new(
+                    &mut d,
+                    &self.backend.thread,
+                    &self.runner,
+                    alpha,
+                    screen_size,
+                )
+                .with_mouse_position(mouse_position),
+            );
+            if let (Some(target), Some(viewport)) = (&self.target, &self.backend.viewport) {
+                unsafe {
+                    ffi::EndTextureMode();
+                }
+                viewport::present(&mut d, target, viewport, self.letterbox_color);
+            }
+            let draw_time = started.elapsed();
+
+            if screenshot {
+                self.capture.screenshot(&self.backend.thread);
+            }
+            self.capture
+                .record(&self.backend.thread, d.get_frame_time());
+
+            // Drawn after capturing so screenshots and recordings stay clean.
+            self.overlay
+                .record(d.get_frame_time(), update_time, draw_time);
+            let (screen_width, fps) = (d.get_screen_width(), d.get_fps());
+            self.overlay
+                .draw(&mut d, &self.runner.services.watches, screen_width, fps);
+
+            self.runner.end_frame();
         }
 
+        self.capture.stop();
+        self.runner.shutdown(&mut self.core);
+
         self
     }
+
+    /// raylib 3.5 can only limit how small a window gets, so windows that grew too large are shrunk back.
+    fn limit_window_size(&mut self) {
+        let (max_width, max_height) = match self.max_size {
+            Some(size) => size,
+            None => return,
+        };
+
+        let rl = &mut self.backend.rl;
+        if rl.is_window_resized() && !rl.is_window_fullscreen() {
+            let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+            if width > max_width || height > max_height {
+                rl.set_window_size(width.min(max_width), height.min(max_height));
+            }
+        }
+    }
+
+    fn pressed(&self, key: Option<KeyboardKey>) -> bool {
+        key.is_some_and(|key| self.backend.is_key_pressed(key))
+    }
 }
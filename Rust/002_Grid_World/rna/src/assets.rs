@@ -0,0 +1,216 @@
+use raylib::prelude::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::backend::Backend;
+
+/// Anything that can be loaded from a file and kept in `Assets`.
+pub trait Asset: Sized + 'static {
+    fn load(path: &Path, backend: &mut dyn Backend) -> Result<Self, Box<dyn Error>>;
+}
+
+impl Asset for String {
+    fn load(path: &Path, _: &mut dyn Backend) -> Result<Self, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+impl Asset for Vec<u8> {
+    fn load(path: &Path, _: &mut dyn Backend) -> Result<Self, Box<dyn Error>> {
+        Ok(fs::read(path)?)
+    }
+}
+
+impl Asset for Texture2D {
+    fn load(path: &Path, backend: &mut dyn Backend) -> Result<Self, Box<dyn Error>> {
+        let (rl, thread) = backend
+            .graphics()
+            .ok_or("Textures can only be loaded while a window is open.")?;
+
+        Ok(rl.load_texture(thread, &path.to_string_lossy())?)
+    }
+}
+
+impl Asset for Font {
+    fn load(path: &Path, backend: &mut dyn Backend) -> Result<Self, Box<dyn Error>> {
+        let (rl, thread) = backend
+            .graphics()
+            .ok_or("Fonts can only be loaded while a window is open.")?;
+
+        Ok(rl.load_font(thread, &path.to_string_lossy())?)
+    }
+}
+
+/// Refers to an asset inside `Assets`; it stays valid when the asset is reloaded.
+pub struct Handle<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Handle({})", self.index)
+    }
+}
+
+type Loader = fn(&Path, &mut dyn Backend) -> Result<Box<dyn Any>, Box<dyn Error>>;
+
+/// When a file was last modified and how long it was; either changing means the file was written to.
+type Stamp = (Option<SystemTime>, u64);
+
+fn load_any<T: Asset>(
+    path: &Path,
+    backend: &mut dyn Backend,
+) -> Result<Box<dyn Any>, Box<dyn Error>> {
+    Ok(Box::new(T::load(path, backend)?))
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.modified().ok(), metadata.len()))
+}
+
+struct Entry {
+    path: PathBuf,
+    stamp: Option<Stamp>,
+    value: Box<dyn Any>,
+    loader: Loader,
+}
+
+/// Files loaded by path, cached behind handles and reloaded whenever they change on disk.
+pub struct Assets {
+    entries: Vec<Entry>,
+    indices: HashMap<(PathBuf, TypeId), usize>,
+    poll_interval: Option<f32>,
+    last_poll: f64,
+}
+
+impl Assets {
+    pub(crate) fn new(poll_interval: Option<f32>) -> Self {
+        Assets {
+            entries: Vec::new(),
+            indices: HashMap::new(),
+            poll_interval,
+            last_poll: 0.0,
+        }
+    }
+
+    /// Loads the file at `path`, unless it was already loaded as a `T`, in which case the same handle is returned.
+    pub fn load<T: Asset>(
+        &mut self,
+        backend: &mut dyn Backend,
+        path: &str,
+    ) -> Result<Handle<T>, Box<dyn Error>> {
+        let path = PathBuf::from(path);
+        let key = (path, TypeId::of::<T>());
+
+        if let Some(index) = self.indices.get(&key) {
+            return Ok(Handle {
+                index: *index,
+                marker: PhantomData,
+            });
+        }
+
+        let stamp = stamp(&key.0);
+        let value = load_any::<T>(&key.0, backend)?;
+        let index = self.entries.len();
+
+        self.entries.push(Entry {
+            path: key.0.clone(),
+            stamp,
+            value,
+            loader: load_any::<T>,
+        });
+        self.indices.insert(key, index);
+
+        Ok(Handle {
+            index,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn get<T: Asset>(&self, handle: Handle<T>) -> Option<&T> {
+        self.entries
+            .get(handle.index)
+            .and_then(|entry| entry.value.downcast_ref())
+    }
+
+    pub fn path<T>(&self, handle: Handle<T>) -> Option<&Path> {
+        self.entries
+            .get(handle.index)
+            .map(|entry| entry.path.as_path())
+    }
+
+    /// Reloads every asset whose file changed since it was last loaded and returns their paths.
+    ///
+    /// An asset that fails to reload, e.g. because its file is only half written, keeps its previous value.
+    pub fn reload_changed(&mut self, backend: &mut dyn Backend) -> Vec<PathBuf> {
+        let mut reloaded = Vec::new();
+
+        for entry in self.entries.iter_mut() {
+            let stamp = match stamp(&entry.path) {
+                Some(stamp) => stamp,
+                // The file may be in the middle of being replaced; check again later.
+                None => continue,
+            };
+
+            if entry.stamp == Some(stamp) {
+                continue;
+            }
+            entry.stamp = Some(stamp);
+
+            match (entry.loader)(&entry.path, backend) {
+                Ok(value) => {
+                    entry.value = value;
+                    reloaded.push(entry.path.clone());
+                }
+                Err(error) => eprintln!(
+                    "Could not reload '{}': {}",
+                    entry.path.to_string_lossy(),
+                    error
+                ),
+            }
+        }
+
+        reloaded
+    }
+
+    /// Calls `reload_changed`, at most once per poll interval.
+    pub(crate) fn poll(&mut self, backend: &mut dyn Backend) -> Vec<PathBuf> {
+        let interval = match self.poll_interval {
+            Some(interval) => interval as f64,
+            None => return Vec::new(),
+        };
+
+        let time = backend.get_time();
+        if time - self.last_poll < interval {
+            return Vec::new();
+        }
+        self.last_poll = time;
+
+        self.reload_changed(backend)
+    }
+}
@@ -0,0 +1,378 @@
+use raylib::prelude::*;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CaptureFormat {
+    /// Every frame is saved as its own numbered PNG inside a folder.
+    PngSequence,
+    /// Every frame is appended to a single looping GIF.
+    Gif,
+}
+
+/// A frame on its way to the GIF, as tightly packed RGBA, and how many hundredths of a second it stays on screen.
+struct GifFrame {
+    pixels: Vec<u8>,
+    delay: u16,
+}
+
+/// Encodes a GIF on a thread of its own, as quantizing every frame would stall the app while it records.
+struct GifWriter {
+    sender: Sender<GifFrame>,
+    worker: JoinHandle<Result<(), String>>,
+}
+
+impl GifWriter {
+    fn start(path: &Path, width: u16, height: u16) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut encoder = create_gif(path, width, height)?;
+        let (sender, receiver) = mpsc::channel::<GifFrame>();
+
+        let worker = thread::spawn(move || {
+            for mut frame in receiver {
+                let mut gif_frame =
+                    gif::Frame::from_rgba_speed(width, height, &mut frame.pixels, 10);
+                gif_frame.delay = frame.delay;
+                encoder
+                    .write_frame(&gif_frame)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            // Dropping the encoder writes the GIF trailer.
+            Ok(())
+        });
+
+        Ok(GifWriter { sender, worker })
+    }
+
+    /// Fails once the worker has given up, e.g. because the disk is full.
+    fn write(&self, frame: GifFrame) -> Result<(), Box<dyn std::error::Error>> {
+        self.sender
+            .send(frame)
+            .map_err(|_| "the GIF encoder stopped unexpectedly".into())
+    }
+
+    /// Waits until every frame that was sent has been written.
+    fn finish(self) -> Result<(), String> {
+        drop(self.sender);
+
+        self.worker
+            .join()
+            .unwrap_or_else(|_| Err(String::from("the GIF encoder panicked")))
+    }
+}
+
+enum Output {
+    Folder(PathBuf),
+    /// The GIF is only created once the first frame shows how large it is.
+    Gif(Option<GifWriter>),
+}
+
+struct Recording {
+    path: PathBuf,
+    output: Output,
+    size: Option<(i32, i32)>,
+    elapsed: f32,
+    captured: usize,
+    frames: usize,
+}
+
+impl Recording {
+    fn new(path: PathBuf, output: Output) -> Self {
+        Recording {
+            path,
+            output,
+            size: None,
+            elapsed: 0.0,
+            captured: 0,
+            frames: 0,
+        }
+    }
+
+    /// Takes the size of the first frame, which is in pixels rather than window units on high-DPI screens.
+    /// Frames of any other size cannot be part of the same recording.
+    fn fits(&mut self, width: i32, height: i32) -> bool {
+        *self.size.get_or_insert((width, height)) == (width, height)
+    }
+}
+
+/// Saves screenshots and recordings of whatever the app drew last.
+pub(crate) struct Capture {
+    directory: PathBuf,
+    format: CaptureFormat,
+    frame_rate: u32,
+    duration: Option<f32>,
+    recording: Option<Recording>,
+}
+
+impl Capture {
+    pub(crate) fn new(
+        directory: &str,
+        format: CaptureFormat,
+        frame_rate: u32,
+        duration: Option<f32>,
+    ) -> Self {
+        Capture {
+            directory: PathBuf::from(directory),
+            format,
+            frame_rate: frame_rate.max(1),
+            duration,
+            recording: None,
+        }
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub(crate) fn screenshot(&self, thread: &RaylibThread) {
+        let path = self
+            .directory
+            .join(format!("screenshot-{}.png", timestamp()));
+
+        if let Err(error) = fs::create_dir_all(&self.directory) {
+            eprintln!(
+                "Could not save screenshot to '{}': {}",
+                path.display(),
+                error
+            );
+            return;
+        }
+
+        screen_data(thread).export_image(&path.to_string_lossy());
+        println!("Saved screenshot to '{}'", path.display());
+    }
+
+    pub(crate) fn start(&mut self) {
+        if self.recording.is_some() {
+            return;
+        }
+
+        let name = format!("recording-{}", timestamp());
+        let result = match self.format {
+            CaptureFormat::PngSequence => {
+                let path = self.directory.join(name);
+                fs::create_dir_all(&path).map(|_| (path.clone(), Output::Folder(path)))
+            }
+            CaptureFormat::Gif => {
+                let path = self.directory.join(name + ".gif");
+                fs::create_dir_all(&self.directory).map(|_| (path, Output::Gif(None)))
+            }
+        };
+
+        match result {
+            Ok((path, output)) => {
+                println!("Recording to '{}'", path.display());
+                self.recording = Some(Recording::new(path, output));
+            }
+            Err(error) => eprintln!("Could not start recording: {}", error),
+        }
+    }
+
+    pub(crate) fn stop(&mut self) {
+        let recording = match self.recording.take() {
+            Some(recording) => recording,
+            None => return,
+        };
+
+        if let Output::Gif(Some(writer)) = recording.output {
+            if let Err(error) = writer.finish() {
+                eprintln!(
+                    "Could not save recording to '{}': {}",
+                    recording.path.display(),
+                    error
+                );
+                return;
+            }
+        }
+
+        println!(
+            "Saved {} frames to '{}'",
+            recording.frames,
+            recording.path.display()
+        );
+    }
+
+    /// Captures however many frames at the recording frame rate fit into the time that has passed.
+    pub(crate) fn record(&mut self, thread: &RaylibThread, frame_time: f32) {
+        let recording = match &mut self.recording {
+            Some(recording) => recording,
+            None => return,
+        };
+
+        let interval = 1.0 / self.frame_rate as f32;
+        let due = (recording.elapsed / interval).floor() as usize + 1;
+        recording.elapsed += frame_time;
+
+        // A slow frame stays on screen longer, so it is repeated until the recording catches up.
+        let repeats = due.saturating_sub(recording.captured);
+        if repeats > 0 {
+            let image = screen_data(thread);
+            if !recording.fits(image.width(), image.height()) {
+                eprintln!("The window changed size, so the recording was stopped.");
+                self.stop();
+                return;
+            }
+
+            if let Err(error) = write_frame(recording, &image, repeats, self.frame_rate) {
+                eprintln!("Could not record frame: {}", error);
+                self.stop();
+                return;
+            }
+            recording.captured += repeats;
+        }
+
+        if let Some(duration) = self.duration {
+            if recording.elapsed >= duration {
+                self.stop();
+            }
+        }
+    }
+}
+
+/// Reads back the frame that is about to be presented.
+///
+/// This has to happen while drawing, where only a shared `RaylibHandle` is available.
+fn screen_data(_: &RaylibThread) -> Image {
+    unsafe { Image::from_raw(raylib::ffi::GetScreenData()) }
+}
+
+fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+}
+
+/// Where the frame with the given `index` of a PNG sequence is saved; the numbers keep the files in order.
+fn frame_path(folder: &Path, index: usize) -> PathBuf {
+    folder.join(format!("frame-{:05}.png", index))
+}
+
+/// How long the frames from `captured` up to `captured + repeats` stay on screen, in hundredths of a second.
+///
+/// GIF delays are whole hundredths of a second, so the rounding is spread across frames.
+fn gif_delay(captured: usize, repeats: usize, frame_rate: u32) -> u16 {
+    let centiseconds = |frames: usize| frames as u32 * 100 / frame_rate;
+
+    (centiseconds(captured + repeats) - centiseconds(captured)) as u16
+}
+
+fn create_gif(
+    path: &Path,
+    width: u16,
+    height: u16,
+) -> Result<gif::Encoder<File>, Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut encoder = gif::Encoder::new(File::create(path)?, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    Ok(encoder)
+}
+
+fn write_frame(
+    recording: &mut Recording,
+    image: &Image,
+    repeats: usize,
+    frame_rate: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match &mut recording.output {
+        Output::Folder(path) => {
+            for _ in 0..repeats {
+                let file = frame_path(path, recording.frames);
+                image.export_image(&file.to_string_lossy());
+                recording.frames += 1;
+            }
+        }
+        Output::Gif(writer) => {
+            let writer = match writer {
+                Some(writer) => writer,
+                None => writer.insert(GifWriter::start(
+                    &recording.path,
+                    image.width() as u16,
+                    image.height() as u16,
+                )?),
+            };
+
+            let pixels = image
+                .get_image_data()
+                .iter()
+                .flat_map(|color| [color.r, color.g, color.b, color.a])
+                .collect();
+
+            writer.write(GifFrame {
+                pixels,
+                delay: gif_delay(recording.captured, repeats, frame_rate),
+            })?;
+            recording.frames += 1;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn png_sequences_are_numbered_in_order() {
+        let folder = Path::new("captures").join("recording-1");
+
+        assert_eq!(frame_path(&folder, 0), folder.join("frame-00000.png"));
+        assert_eq!(frame_path(&folder, 123), folder.join("frame-00123.png"));
+        assert!(frame_path(&folder, 9) < frame_path(&folder, 10));
+    }
+
+    #[test]
+    fn recordings_keep_the_size_of_their_first_frame() {
+        let mut recording = Recording::new(PathBuf::from("recording"), Output::Gif(None));
+
+        // A high-DPI framebuffer is larger than the window that was asked for.
+        assert!(recording.fits(1280, 720));
+        assert!(recording.fits(1280, 720));
+        assert!(!recording.fits(640, 360));
+        assert_eq!(recording.size, Some((1280, 720)));
+    }
+
+    #[test]
+    fn gif_delays_spread_their_rounding() {
+        let delays: Vec<u16> = (0..3).map(|captured| gif_delay(captured, 1, 30)).collect();
+
+        assert_eq!(delays, vec![3, 3, 4]);
+        assert_eq!(gif_delay(0, 30, 30), 100);
+    }
+
+    #[test]
+    fn gifs_are_encoded_in_the_background() {
+        let path = std::env::temp_dir().join(format!("rna-{}-capture.gif", std::process::id()));
+
+        let writer = GifWriter::start(&path, 4, 2).unwrap();
+        for shade in [0, 128, 255].iter() {
+            writer
+                .write(GifFrame {
+                    pixels: vec![*shade; 4 * 2 * 4],
+                    delay: 3,
+                })
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let mut decoder = gif::Decoder::new(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 2));
+
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 3);
+            frames += 1;
+        }
+        assert_eq!(frames, 3);
+
+        fs::remove_file(path).unwrap();
+    }
+}
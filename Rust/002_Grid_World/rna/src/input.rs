@@ -0,0 +1,346 @@
+use raylib::prelude::*;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+
+use super::backend::Backend;
+
+/// Something on the keyboard or mouse that can trigger an action.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Binding {
+    Key(KeyboardKey),
+    MouseButton(MouseButton),
+    WheelUp,
+    WheelDown,
+}
+
+pub(crate) const KEYS: [(&str, KeyboardKey); 105] = [
+    ("APOSTROPHE", KeyboardKey::KEY_APOSTROPHE),
+    ("COMMA", KeyboardKey::KEY_COMMA),
+    ("MINUS", KeyboardKey::KEY_MINUS),
+    ("PERIOD", KeyboardKey::KEY_PERIOD),
+    ("SLASH", KeyboardKey::KEY_SLASH),
+    ("ZERO", KeyboardKey::KEY_ZERO),
+    ("ONE", KeyboardKey::KEY_ONE),
+    ("TWO", KeyboardKey::KEY_TWO),
+    ("THREE", KeyboardKey::KEY_THREE),
+    ("FOUR", KeyboardKey::KEY_FOUR),
+    ("FIVE", KeyboardKey::KEY_FIVE),
+    ("SIX", KeyboardKey::KEY_SIX),
+    ("SEVEN", KeyboardKey::KEY_SEVEN),
+    ("EIGHT", KeyboardKey::KEY_EIGHT),
+    ("NINE", KeyboardKey::KEY_NINE),
+    ("SEMICOLON", KeyboardKey::KEY_SEMICOLON),
+    ("EQUAL", KeyboardKey::KEY_EQUAL),
+    ("A", KeyboardKey::KEY_A),
+    ("B", KeyboardKey::KEY_B),
+    ("C", KeyboardKey::KEY_C),
+    ("D", KeyboardKey::KEY_D),
+    ("E", KeyboardKey::KEY_E),
+    ("F", KeyboardKey::KEY_F),
+    ("G", KeyboardKey::KEY_G),
+    ("H", KeyboardKey::KEY_H),
+    ("I", KeyboardKey::KEY_I),
+    ("J", KeyboardKey::KEY_J),
+    ("K", KeyboardKey::KEY_K),
+    ("L", KeyboardKey::KEY_L),
+    ("M", KeyboardKey::KEY_M),
+    ("N", KeyboardKey::KEY_N),
+    ("O", KeyboardKey::KEY_O),
+    ("P", KeyboardKey::KEY_P),
+    ("Q", KeyboardKey::KEY_Q),
+    ("R", KeyboardKey::KEY_R),
+    ("S", KeyboardKey::KEY_S),
+    ("T", KeyboardKey::KEY_T),
+    ("U", KeyboardKey::KEY_U),
+    ("V", KeyboardKey::KEY_V),
+    ("W", KeyboardKey::KEY_W),
+    ("X", KeyboardKey::KEY_X),
+    ("Y", KeyboardKey::KEY_Y),
+    ("Z", KeyboardKey::KEY_Z),
+    ("SPACE", KeyboardKey::KEY_SPACE),
+    ("ESCAPE", KeyboardKey::KEY_ESCAPE),
+    ("ENTER", KeyboardKey::KEY_ENTER),
+    ("TAB", KeyboardKey::KEY_TAB),
+    ("BACKSPACE", KeyboardKey::KEY_BACKSPACE),
+    ("INSERT", KeyboardKey::KEY_INSERT),
+    ("DELETE", KeyboardKey::KEY_DELETE),
+    ("RIGHT", KeyboardKey::KEY_RIGHT),
+    ("LEFT", KeyboardKey::KEY_LEFT),
+    ("DOWN", KeyboardKey::KEY_DOWN),
+    ("UP", KeyboardKey::KEY_UP),
+    ("PAGE_UP", KeyboardKey::KEY_PAGE_UP),
+    ("PAGE_DOWN", KeyboardKey::KEY_PAGE_DOWN),
+    ("HOME", KeyboardKey::KEY_HOME),
+    ("END", KeyboardKey::KEY_END),
+    ("CAPS_LOCK", KeyboardKey::KEY_CAPS_LOCK),
+    ("SCROLL_LOCK", KeyboardKey::KEY_SCROLL_LOCK),
+    ("NUM_LOCK", KeyboardKey::KEY_NUM_LOCK),
+    ("PRINT_SCREEN", KeyboardKey::KEY_PRINT_SCREEN),
+    ("PAUSE", KeyboardKey::KEY_PAUSE),
+    ("F1", KeyboardKey::KEY_F1),
+    ("F2", KeyboardKey::KEY_F2),
+    ("F3", KeyboardKey::KEY_F3),
+    ("F4", KeyboardKey::KEY_F4),
+    ("F5", KeyboardKey::KEY_F5),
+    ("F6", KeyboardKey::KEY_F6),
+    ("F7", KeyboardKey::KEY_F7),
+    ("F8", KeyboardKey::KEY_F8),
+    ("F9", KeyboardKey::KEY_F9),
+    ("F10", KeyboardKey::KEY_F10),
+    ("F11", KeyboardKey::KEY_F11),
+    ("F12", KeyboardKey::KEY_F12),
+    ("LEFT_SHIFT", KeyboardKey::KEY_LEFT_SHIFT),
+    ("LEFT_CONTROL", KeyboardKey::KEY_LEFT_CONTROL),
+    ("LEFT_ALT", KeyboardKey::KEY_LEFT_ALT),
+    ("LEFT_SUPER", KeyboardKey::KEY_LEFT_SUPER),
+    ("RIGHT_SHIFT", KeyboardKey::KEY_RIGHT_SHIFT),
+    ("RIGHT_CONTROL", KeyboardKey::KEY_RIGHT_CONTROL),
+    ("RIGHT_ALT", KeyboardKey::KEY_RIGHT_ALT),
+    ("RIGHT_SUPER", KeyboardKey::KEY_RIGHT_SUPER),
+    ("KB_MENU", KeyboardKey::KEY_KB_MENU),
+    ("LEFT_BRACKET", KeyboardKey::KEY_LEFT_BRACKET),
+    ("BACKSLASH", KeyboardKey::KEY_BACKSLASH),
+    ("RIGHT_BRACKET", KeyboardKey::KEY_RIGHT_BRACKET),
+    ("GRAVE", KeyboardKey::KEY_GRAVE),
+    ("KP_0", KeyboardKey::KEY_KP_0),
+    ("KP_1", KeyboardKey::KEY_KP_1),
+    ("KP_2", KeyboardKey::KEY_KP_2),
+    ("KP_3", KeyboardKey::KEY_KP_3),
+    ("KP_4", KeyboardKey::KEY_KP_4),
+    ("KP_5", KeyboardKey::KEY_KP_5),
+    ("KP_6", KeyboardKey::KEY_KP_6),
+    ("KP_7", KeyboardKey::KEY_KP_7),
+    ("KP_8", KeyboardKey::KEY_KP_8),
+    ("KP_9", KeyboardKey::KEY_KP_9),
+    ("KP_DECIMAL", KeyboardKey::KEY_KP_DECIMAL),
+    ("KP_DIVIDE", KeyboardKey::KEY_KP_DIVIDE),
+    ("KP_MULTIPLY", KeyboardKey::KEY_KP_MULTIPLY),
+    ("KP_SUBTRACT", KeyboardKey::KEY_KP_SUBTRACT),
+    ("KP_ADD", KeyboardKey::KEY_KP_ADD),
+    ("KP_ENTER", KeyboardKey::KEY_KP_ENTER),
+    ("KP_EQUAL", KeyboardKey::KEY_KP_EQUAL),
+];
+
+pub(crate) const MOUSE_BUTTONS: [(&str, MouseButton); 3] = [
+    ("MOUSE_LEFT", MouseButton::MOUSE_LEFT_BUTTON),
+    ("MOUSE_RIGHT", MouseButton::MOUSE_RIGHT_BUTTON),
+    ("MOUSE_MIDDLE", MouseButton::MOUSE_MIDDLE_BUTTON),
+];
+
+impl Binding {
+    /// Parses names such as `SPACE`, `LEFT_BRACKET`, `MOUSE_LEFT` or `WHEEL_UP`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_uppercase();
+        let name = name.strip_prefix("KEY_").unwrap_or(&name);
+
+        match name {
+            "WHEEL_UP" => return Some(Binding::WheelUp),
+            "WHEEL_DOWN" => return Some(Binding::WheelDown),
+            _ => (),
+        }
+
+        KEYS.iter()
+            .find(|(key_name, _)| *key_name == name)
+            .map(|(_, key)| Binding::Key(*key))
+            .or_else(|| {
+                MOUSE_BUTTONS
+                    .iter()
+                    .find(|(button_name, _)| *button_name == name)
+                    .map(|(_, button)| Binding::MouseButton(*button))
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Binding::Key(key) => KEYS
+                .iter()
+                .find(|(_, other)| other == key)
+                .map_or("UNKNOWN", |(name, _)| name),
+            Binding::MouseButton(button) => MOUSE_BUTTONS
+                .iter()
+                .find(|(_, other)| other == button)
+                .map_or("UNKNOWN", |(name, _)| name),
+            Binding::WheelUp => "WHEEL_UP",
+            Binding::WheelDown => "WHEEL_DOWN",
+        }
+    }
+
+    pub fn is_pressed(&self, backend: &dyn Backend) -> bool {
+        match self {
+            Binding::Key(key) => backend.is_key_pressed(*key),
+            Binding::MouseButton(button) => backend.is_mouse_button_pressed(*button),
+            Binding::WheelUp => backend.get_mouse_wheel_move() > 0.0,
+            Binding::WheelDown => backend.get_mouse_wheel_move() < 0.0,
+        }
+    }
+
+    /// How strongly the binding was triggered this frame; keys and buttons are either zero or one.
+    pub fn value(&self, backend: &dyn Backend) -> f32 {
+        match self {
+            Binding::WheelUp => backend.get_mouse_wheel_move().max(0.0),
+            Binding::WheelDown => (-backend.get_mouse_wheel_move()).max(0.0),
+            _ => {
+                if self.is_pressed(backend) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The wheel has no notion of being held, so wheel bindings are only down on the frame they moved.
+    pub fn is_down(&self, backend: &dyn Backend) -> bool {
+        match self {
+            Binding::Key(key) => backend.is_key_down(*key),
+            Binding::MouseButton(button) => backend.is_mouse_button_down(*button),
+            Binding::WheelUp | Binding::WheelDown => self.is_pressed(backend),
+        }
+    }
+}
+
+/// Maps named actions, e.g. `"toggle_policy"`, onto the bindings that trigger them.
+///
+/// Games ask whether an action happened instead of asking about specific keys, so every action can be rebound
+/// from a file or at runtime.
+#[derive(Clone)]
+pub struct InputMap {
+    bindings: HashMap<String, Vec<Binding>>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        InputMap {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Adds `binding` to whatever already triggers `action`.
+    pub fn bind(&mut self, action: &str, binding: Binding) -> &mut Self {
+        let bindings = self.bindings.entry(String::from(action)).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Makes `binding` the only thing that triggers `action`.
+    pub fn rebind(&mut self, action: &str, binding: Binding) -> &mut Self {
+        self.bindings.insert(String::from(action), vec![binding]);
+        self
+    }
+
+    pub fn unbind(&mut self, action: &str) -> &mut Self {
+        self.bindings.remove(action);
+        self
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.bindings
+            .get(action)
+            .map_or(&[], |bindings| bindings.as_slice())
+    }
+
+    pub fn actions(&self) -> Vec<&str> {
+        let mut actions: Vec<&str> = self.bindings.keys().map(|action| action.as_str()).collect();
+        actions.sort_unstable();
+        actions
+    }
+
+    pub fn is_pressed(&self, backend: &dyn Backend, action: &str) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_pressed(backend))
+    }
+
+    pub fn is_down(&self, backend: &dyn Backend, action: &str) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_down(backend))
+    }
+
+    pub fn value(&self, backend: &dyn Backend, action: &str) -> f32 {
+        self.bindings(action)
+            .iter()
+            .map(|binding| binding.value(backend))
+            .fold(0.0, f32::max)
+    }
+
+    /// Combines two opposing actions, e.g. `zoom_in` and `zoom_out`, into a single value.
+    pub fn axis(&self, backend: &dyn Backend, positive: &str, negative: &str) -> f32 {
+        self.value(backend, positive) - self.value(backend, negative)
+    }
+
+    /// Returns whatever binding was triggered this frame, which is handy when waiting for a new binding.
+    pub fn listen(backend: &dyn Backend) -> Option<Binding> {
+        let mut bindings = KEYS
+            .iter()
+            .map(|(_, key)| Binding::Key(*key))
+            .chain(
+                MOUSE_BUTTONS
+                    .iter()
+                    .map(|(_, button)| Binding::MouseButton(*button)),
+            )
+            .chain([Binding::WheelUp, Binding::WheelDown]);
+
+        bindings.find(|binding| binding.is_pressed(backend))
+    }
+
+    /// Reads bindings from a file where every line is an action followed by a comma separated list of bindings,
+    /// e.g. `zoom_in WHEEL_UP,EQUAL`. Actions in the file replace the bindings they had before.
+    ///
+    /// Nothing changes unless the whole file could be read.
+    pub fn load(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut loaded = self.bindings.clone();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (action, bindings) = line.split_once(char::is_whitespace).ok_or(format!(
+                "line {}: expected an action followed by bindings",
+                number + 1
+            ))?;
+
+            let mut parsed = Vec::new();
+            for name in bindings.split(',') {
+                let binding = Binding::from_name(name).ok_or(format!(
+                    "line {}: unknown binding '{}'",
+                    number + 1,
+                    name.trim()
+                ))?;
+                parsed.push(binding);
+            }
+
+            loaded.insert(String::from(action), parsed);
+        }
+
+        self.bindings = loaded;
+        Ok(())
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
+
+        for action in self.actions() {
+            let names: Vec<&str> = self
+                .bindings(action)
+                .iter()
+                .map(|binding| binding.name())
+                .collect();
+            writeln!(file, "{} {}", action, names.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        InputMap::new()
+    }
+}
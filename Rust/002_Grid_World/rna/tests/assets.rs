@@ -0,0 +1,100 @@
+use rna::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Reader {
+    path: String,
+    text: Option<Handle<String>>,
+    contents: String,
+    reloads: Vec<PathBuf>,
+}
+
+impl Reader {
+    fn new(path: &Path) -> Self {
+        Reader {
+            path: path.to_string_lossy().to_string(),
+            text: None,
+            contents: String::new(),
+            reloads: Vec::new(),
+        }
+    }
+}
+
+impl Core for Reader {
+    fn initialize(&mut self, context: &mut Context) {
+        self.text = context.load(&self.path).ok();
+    }
+    fn update(&mut self, context: &mut Context) {
+        if let Some(text) = self.text.and_then(|handle| context.asset(handle)) {
+            self.contents = text.clone();
+        }
+    }
+    fn draw(&self, _: &mut Frame) {}
+
+    fn on_asset_reloaded(&mut self, _: &mut Context, path: &Path) {
+        self.reloads.push(path.to_path_buf());
+    }
+}
+
+fn temporary_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rna-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn changed_files_are_reloaded() {
+    let path = temporary_file("reload.txt", "Dimension 4,3");
+    let mut app = HeadlessApp::new(Reader::new(&path), 320, 240);
+
+    app.run(2);
+    assert_eq!(app.core().contents, "Dimension 4,3");
+    assert!(app.core().reloads.is_empty());
+
+    fs::write(&path, "Dimension 10,10").unwrap();
+    app.run(2);
+    assert_eq!(app.core().contents, "Dimension 10,10");
+    assert_eq!(app.core().reloads, vec![path.clone()]);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn loading_twice_returns_the_same_handle() {
+    struct Twice;
+
+    impl Core for Twice {
+        fn initialize(&mut self, context: &mut Context) {
+            let path = temporary_file("twice.txt", "Wall 1,1");
+            let path = path.to_string_lossy();
+
+            let first: Handle<String> = context.load(&path).unwrap();
+            let second: Handle<String> = context.load(&path).unwrap();
+            let bytes: Handle<Vec<u8>> = context.load(&path).unwrap();
+
+            assert_eq!(first, second);
+            assert_eq!(context.asset(bytes).unwrap().as_slice(), b"Wall 1,1");
+
+            fs::remove_file(path.as_ref()).unwrap();
+        }
+        fn update(&mut self, _: &mut Context) {}
+        fn draw(&self, _: &mut Frame) {}
+    }
+
+    HeadlessApp::new(Twice, 320, 240).step();
+}
+
+#[test]
+fn missing_files_are_errors() {
+    struct Missing;
+
+    impl Core for Missing {
+        fn initialize(&mut self, context: &mut Context) {
+            assert!(context.load::<String>("missing.txt").is_err());
+        }
+        fn update(&mut self, _: &mut Context) {}
+        fn draw(&self, _: &mut Frame) {}
+    }
+
+    HeadlessApp::new(Missing, 320, 240).step();
+}
@@ -0,0 +1,56 @@
+use raylib::prelude::*;
+use rna::*;
+
+#[test]
+fn fit_fills_one_axis_and_letterboxes_the_other() {
+    let viewport = Viewport::new(320, 180, ScaleMode::Fit);
+
+    assert_eq!(viewport.scale(1000, 1000), 3.125);
+    let destination = viewport.destination(1000, 1000);
+    assert_eq!(
+        (destination.x, destination.y, destination.width),
+        (0.0, 218.0, 1000.0)
+    );
+}
+
+#[test]
+fn integer_scaling_uses_whole_multiples() {
+    let viewport = Viewport::new(320, 180, ScaleMode::Integer);
+
+    assert_eq!(viewport.scale(1000, 1000), 3.0);
+    let destination = viewport.destination(1000, 1000);
+    assert_eq!(
+        (
+            destination.x,
+            destination.y,
+            destination.width,
+            destination.height
+        ),
+        (20.0, 230.0, 960.0, 540.0)
+    );
+
+    // Too small a window would otherwise round down to nothing.
+    assert_eq!(viewport.scale(160, 90), 0.5);
+}
+
+#[test]
+fn mouse_positions_are_translated_into_logical_space() {
+    let viewport = Viewport::new(320, 180, ScaleMode::Integer);
+
+    let center = viewport.to_logical(1000, 1000, Vector2::new(500.0, 500.0));
+    assert_eq!((center.x, center.y), (160.0, 90.0));
+
+    let corner = viewport.to_logical(1000, 1000, Vector2::new(20.0, 230.0));
+    assert_eq!((corner.x, corner.y), (0.0, 0.0));
+
+    let letterbox = viewport.to_logical(1000, 1000, Vector2::new(500.0, 10.0));
+    assert!(letterbox.y < 0.0);
+}
+
+#[test]
+fn minimized_windows_put_the_mouse_at_the_origin() {
+    let viewport = Viewport::new(320, 180, ScaleMode::Fit);
+
+    let mouse = viewport.to_logical(0, 0, Vector2::new(12.0, 34.0));
+    assert_eq!((mouse.x, mouse.y), (0.0, 0.0));
+}